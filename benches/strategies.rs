@@ -0,0 +1,61 @@
+//! Measures how long a round takes for each family of strategies, run with `cargo bench`.
+//!
+//! Strategies that looked back over the whole history on every move, such as the majority
+//! strategies and Grim Trigger, used to make a game quadratic in its length. They now read the
+//! running totals in their `HistoryView` instead, so every strategy here should take about as
+//! long per round over ten times `NUM_TURNS` rounds as over `NUM_TURNS`. One whose cost per
+//! round grows with the length of the game has gone back to rescanning the history.
+//!
+//! The standard library has no benchmark harness on stable Rust, so this is a plain program that
+//! repeats each game until enough time has passed and reports the mean.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use prisoners_dilemma_competition::*;
+
+/// The least time to spend repeating each game
+const MEASUREMENT_TIME: Duration = Duration::from_millis(500);
+
+fn main() {
+    println!(
+        "{:<24}  {:>12}  {:>12}",
+        "Strategy", "NUM_TURNS", "10 NUM_TURNS"
+    );
+    bench::<TitForTat>();
+    bench::<Pavlov>();
+    bench::<Gradual>();
+    bench::<SoftMajority>();
+    bench::<HardMajority>();
+    bench::<GrimTrigger>();
+}
+
+/// Time a game of the strategy `S` against itself at both lengths and print the time per round.
+fn bench<S>()
+where
+    S: Strategy + 'static,
+    S::State: 'static,
+{
+    let short = time_game::<S>(NUM_TURNS) / NUM_TURNS as u32;
+    let long = time_game::<S>(10 * NUM_TURNS) / (10 * NUM_TURNS) as u32;
+    println!("{:<24}  {:>12.2?}  {:>12.2?}", S::NAME, short, long);
+}
+
+/// The mean time taken to play a game of `rounds` rounds between two players following `S`.
+fn time_game<S>(rounds: usize) -> Duration
+where
+    S: Strategy + 'static,
+    S::State: 'static,
+{
+    let mut games = 0;
+    let start = Instant::now();
+    while start.elapsed() < MEASUREMENT_TIME {
+        let mut game = RepeatedPrisonersDilemma::<S, S>::new();
+        game.play_n_rounds(black_box(rounds));
+        black_box(game.calculate_score());
+        games += 1;
+    }
+    start.elapsed() / games
+}
@@ -0,0 +1,742 @@
+//! Statistics describing how a game played out, beyond the final score.
+
+use std::{collections::HashMap, fmt, ops::Range};
+
+use crate::{
+    outcome, AlwaysCooperate, CooperateOrDefect, CooperateOrDefect::*, DynGame, DynStrategy,
+    Entrant, MemoryOne, Outcome, PayoffMatrix, Player, RepeatedPrisonersDilemma, Rng, Strategy,
+    StrategyFailure, StrategyInstance, NUM_TURNS,
+};
+
+/// How many rounds ended in each of the four possible outcomes. The first letter is player 1's
+/// move and the second is player 2's, so `cd` counts the rounds where player 1 cooperated and
+/// player 2 defected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OutcomeCounts {
+    pub cc: usize,
+    pub cd: usize,
+    pub dc: usize,
+    pub dd: usize,
+}
+
+/// A summary of a game: who played, how it was scored, and how cooperative each player was.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameResult {
+    pub p1_name: String,
+    pub p2_name: String,
+    pub p1_score: isize,
+    pub p2_score: isize,
+    pub rounds: usize,
+    pub p1_coop_rate: f64,
+    pub p2_coop_rate: f64,
+    /// Every round that a player forfeited because it failed to choose a move
+    pub failures: Vec<StrategyFailure>,
+}
+
+/// Every statistic about a single game in one place, for showing the result of a matchup. The
+/// `Display` impl prints it as a short block of text, one statistic per line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchSummary {
+    pub p1_name: String,
+    pub p2_name: String,
+    pub rounds: usize,
+    pub scores: (isize, isize),
+    /// The average payout per round, see
+    /// [`normalized_score`](RepeatedPrisonersDilemma::normalized_score)
+    pub normalized_scores: (f64, f64),
+    pub cooperation_rates: (f64, f64),
+    pub outcome_counts: OutcomeCounts,
+    pub longest_mutual_cooperation: usize,
+    /// See [`first_defection`](RepeatedPrisonersDilemma::first_defection)
+    pub first_defection: Option<(Player, usize)>,
+}
+
+impl fmt::Display for MatchSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let OutcomeCounts { cc, cd, dc, dd } = self.outcome_counts;
+        writeln!(
+            f,
+            "{} vs {}, {}",
+            self.p1_name,
+            self.p2_name,
+            rounds(self.rounds)
+        )?;
+        writeln!(f, "Score: {} to {}", self.scores.0, self.scores.1)?;
+        writeln!(
+            f,
+            "Average per round: {:.2} to {:.2}",
+            self.normalized_scores.0, self.normalized_scores.1
+        )?;
+        writeln!(
+            f,
+            "Cooperation: {:.1}% to {:.1}%",
+            100.0 * self.cooperation_rates.0,
+            100.0 * self.cooperation_rates.1
+        )?;
+        writeln!(f, "Outcomes: CC {cc}, CD {cd}, DC {dc}, DD {dd}")?;
+        writeln!(
+            f,
+            "Longest mutual cooperation: {}",
+            rounds(self.longest_mutual_cooperation)
+        )?;
+        match self.first_defection {
+            Some((player, round)) => {
+                let name = match player {
+                    Player::One => &self.p1_name,
+                    Player::Two => &self.p2_name,
+                };
+                writeln!(f, "First to defect: {name} in round {round}")
+            }
+            None => writeln!(f, "First to defect: nobody"),
+        }
+    }
+}
+
+/// A number of rounds, such as "1 round" or "5 rounds".
+fn rounds(count: usize) -> String {
+    match count {
+        1 => "1 round".to_string(),
+        count => format!("{count} rounds"),
+    }
+}
+
+impl<P1, P2> RepeatedPrisonersDilemma<P1, P2> {
+    /// Gather every statistic about the rounds played so far, see [`MatchSummary`]. Every
+    /// statistic has a well defined value before any rounds have been played, so a game from a
+    /// tournament with no rounds can be summarized like any other:
+    ///
+    /// ```
+    /// # use prisoners_dilemma_competition::{
+    /// #     AlwaysDefect, GamePattern, OutcomeCounts, RepeatedPrisonersDilemma, TitForTat,
+    /// # };
+    /// let game = RepeatedPrisonersDilemma::<TitForTat, AlwaysDefect>::new();
+    /// let summary = game.summary();
+    /// assert_eq!(summary.rounds, 0);
+    /// assert_eq!(summary.scores, (0, 0));
+    /// assert_eq!(summary.normalized_scores, (0.0, 0.0));
+    /// assert_eq!(summary.cooperation_rates, (0.0, 0.0));
+    /// assert_eq!(summary.outcome_counts, OutcomeCounts::default());
+    /// assert_eq!(summary.longest_mutual_cooperation, 0);
+    /// assert_eq!(summary.first_defection, None);
+    ///
+    /// assert!(game.outcomes().is_empty());
+    /// assert!(game.round_payoffs().is_empty());
+    /// assert!(game.cumulative_cooperation().is_empty());
+    /// assert!(game.reconciliations(3).is_empty());
+    /// assert_eq!(game.settled_pattern(10), GamePattern::Irregular);
+    /// assert_eq!(game.calculate_discounted_score(0.9), (0.0, 0.0));
+    /// assert_eq!(game.to_csv().lines().count(), 1);
+    /// assert_eq!(game.result().p1_coop_rate, 0.0);
+    /// ```
+    pub fn summary(&self) -> MatchSummary {
+        let (p1_name, p2_name) = self.names();
+        MatchSummary {
+            p1_name: p1_name.to_string(),
+            p2_name: p2_name.to_string(),
+            rounds: self.player_1_moves.len(),
+            scores: self.calculate_score(),
+            normalized_scores: self.normalized_score(),
+            cooperation_rates: self.cooperation_rates(),
+            outcome_counts: self.outcome_counts(),
+            longest_mutual_cooperation: self.longest_mutual_cooperation(),
+            first_defection: self.first_defection(),
+        }
+    }
+
+    /// Summarize the rounds played so far.
+    pub fn result(&self) -> GameResult {
+        let (p1_name, p2_name) = self.names();
+        let (p1_score, p2_score) = self.calculate_score();
+        let (p1_coop_rate, p2_coop_rate) = self.cooperation_rates();
+        GameResult {
+            p1_name: p1_name.to_string(),
+            p2_name: p2_name.to_string(),
+            p1_score,
+            p2_score,
+            rounds: self.player_1_moves.len(),
+            p1_coop_rate,
+            p2_coop_rate,
+            failures: self.failures().to_vec(),
+        }
+    }
+
+    /// The fraction of rounds in which player 1 and player 2 cooperated. Both rates are `0.0` for
+    /// a game in which no rounds have been played yet.
+    pub fn cooperation_rates(&self) -> (f64, f64) {
+        (
+            cooperation_rate(&self.player_1_moves),
+            cooperation_rate(&self.player_2_moves),
+        )
+    }
+
+    /// Count the rounds that ended in each outcome. The counts add up to the number of rounds
+    /// played, so they are all 0 before the first round.
+    pub fn outcome_counts(&self) -> OutcomeCounts {
+        let mut counts = OutcomeCounts::default();
+        for moves in self.player_1_moves.iter().zip(&self.player_2_moves) {
+            match moves {
+                (Cooperate, Cooperate) => counts.cc += 1,
+                (Cooperate, Defect) => counts.cd += 1,
+                (Defect, Cooperate) => counts.dc += 1,
+                (Defect, Defect) => counts.dd += 1,
+            }
+        }
+        counts
+    }
+
+    /// The length of the longest run of consecutive rounds in which both players cooperated, or 0
+    /// if they never did.
+    pub fn longest_mutual_cooperation(&self) -> usize {
+        let mut longest = 0;
+        let mut current = 0;
+        for moves in self.player_1_moves.iter().zip(&self.player_2_moves) {
+            if let (Cooperate, Cooperate) = moves {
+                current += 1;
+                longest = longest.max(current);
+            } else {
+                current = 0;
+            }
+        }
+        longest
+    }
+
+    /// For each round played so far, the fraction of the rounds up to and including it in which
+    /// both players cooperated, for plotting whether cooperation settles down over a game. The
+    /// first element is for round 1, and the result is empty before any rounds have been played.
+    pub fn cumulative_cooperation(&self) -> Vec<f64> {
+        let mut mutual = 0;
+        self.player_1_moves
+            .iter()
+            .zip(&self.player_2_moves)
+            .enumerate()
+            .map(|(round, moves)| {
+                if let (Cooperate, Cooperate) = moves {
+                    mutual += 1;
+                }
+                mutual as f64 / (round + 1) as f64
+            })
+            .collect()
+    }
+
+    /// The player who defected first and the round in which they did, counting from 1, or `None`
+    /// if neither player has defected. A strategy that is never the first to defect is called
+    /// nice. If both players first defected in the same round, player 1 is reported.
+    pub fn first_defection(&self) -> Option<(Player, usize)> {
+        self.player_1_moves
+            .iter()
+            .zip(&self.player_2_moves)
+            .enumerate()
+            .find_map(|(round, moves)| match moves {
+                (Defect, _) => Some((Player::One, round + 1)),
+                (Cooperate, Defect) => Some((Player::Two, round + 1)),
+                (Cooperate, Cooperate) => None,
+            })
+    }
+
+    /// Classify the pattern that the last `window` rounds of the game follow, or the whole game
+    /// if it is shorter, for sorting out the unusual matchups in a large tournament. The rounds
+    /// have a pattern if they repeat a cycle of at most half of their length, and a one-sided
+    /// pattern, in which only one player is ever exploited, is reported as exploitative. Fewer
+    /// than two rounds are too few to repeat, so they are irregular.
+    pub fn settled_pattern(&self, window: usize) -> GamePattern {
+        let outcomes = self.outcomes();
+        let recent = &outcomes[outcomes.len().saturating_sub(window)..];
+        let Some(period) = (1..=recent.len() / 2)
+            .find(|&period| recent.iter().zip(&recent[period..]).all(|(a, b)| a == b))
+        else {
+            return GamePattern::Irregular;
+        };
+
+        let cycle = &recent[..period];
+        let exploited = |outcome: Outcome| cycle.contains(&outcome);
+        match (
+            exploited(Outcome::P2Exploited),
+            exploited(Outcome::P1Exploited),
+        ) {
+            (true, false) => GamePattern::Exploitative {
+                exploiter: Player::One,
+            },
+            (false, true) => GamePattern::Exploitative {
+                exploiter: Player::Two,
+            },
+            _ if period == 1 => GamePattern::Stable { outcome: cycle[0] },
+            _ => GamePattern::Oscillating,
+        }
+    }
+
+    /// Every time cooperation broke down into mutual defection and then recovered, in the order
+    /// they happened. A breakdown starts with a round of mutual defection, and it counts as
+    /// recovered once the players cooperate with each other for `sustained` rounds in a row.
+    /// Shorter spells of mutual cooperation, and further mutual defections, are part of the same
+    /// breakdown. A breakdown that has not recovered by the end of the game is not included.
+    pub fn reconciliations(&self, sustained: usize) -> Vec<Reconciliation> {
+        let mut reconciliations = Vec::new();
+        // The round the current breakdown started in, counting from 0, if there is one
+        let mut breakdown = None;
+        let mut cooperating = 0;
+        for (round, moves) in self
+            .player_1_moves
+            .iter()
+            .zip(&self.player_2_moves)
+            .enumerate()
+        {
+            match moves {
+                (Cooperate, Cooperate) => cooperating += 1,
+                (Defect, Defect) => {
+                    breakdown.get_or_insert(round);
+                    cooperating = 0;
+                }
+                _ => cooperating = 0,
+            }
+
+            if let Some(start) = breakdown {
+                if cooperating == sustained.max(1) {
+                    reconciliations.push(Reconciliation {
+                        start: start + 1,
+                        length: round + 1 - cooperating - start,
+                    });
+                    breakdown = None;
+                }
+            }
+        }
+        reconciliations
+    }
+}
+
+/// A breakdown of cooperation that the players recovered from, see
+/// [`RepeatedPrisonersDilemma::reconciliations`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reconciliation {
+    /// The round of the first mutual defection of the breakdown, counting from 1
+    pub start: usize,
+    /// The number of rounds from the start of the breakdown until mutual cooperation resumed,
+    /// not counting the cooperative rounds
+    pub length: usize,
+}
+
+/// What a game settled into, see [`RepeatedPrisonersDilemma::settled_pattern`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GamePattern {
+    /// The same symmetric outcome every round, either mutual cooperation or mutual defection
+    Stable { outcome: Outcome },
+    /// A repeating cycle in which each player exploits the other, or neither does
+    Oscillating,
+    /// A repeating cycle, possibly of a single round, in which one player exploits the other
+    /// and is never exploited back
+    Exploitative { exploiter: Player },
+    /// No repeating pattern
+    Irregular,
+}
+
+fn cooperation_rate(moves: &[CooperateOrDefect]) -> f64 {
+    if moves.is_empty() {
+        return 0.0;
+    }
+    let cooperations = moves.iter().filter(|m| matches!(m, Cooperate)).count();
+    cooperations as f64 / moves.len() as f64
+}
+
+/// The traits that Axelrod found the successful strategies in his tournaments had in common,
+/// see [`classify`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StrategyTraits {
+    /// Never the first to defect
+    pub nice: bool,
+    /// Answers a defection with a defection of its own
+    pub retaliatory: bool,
+    /// Goes back to cooperating once the opponent does
+    pub forgiving: bool,
+    /// Easy for the opponent to read, because every move follows from the previous round alone
+    pub clear: bool,
+}
+
+/// The number of rounds in each of the games that [`classify`] plays
+const PROBE_ROUNDS: usize = 20;
+
+/// The rounds, counting from 0, in which the provoking opponent defects
+const PROVOCATION: Range<usize> = 5..7;
+
+/// How many rounds after the provocation a strategy has to retaliate in
+const RETALIATION_WINDOW: usize = 3;
+
+/// An opponent that cooperates except in the rounds from `START` up to but not including `END`,
+/// counting from 0, when it defects.
+struct Provoker<const START: usize, const END: usize>;
+
+impl<const START: usize, const END: usize> Strategy for Provoker<START, END> {
+    const NAME: &'static str = "Provoker";
+    type State = ();
+
+    fn next_move(
+        my_moves: &[CooperateOrDefect],
+        _their_moves: &[CooperateOrDefect],
+        _state: &mut (),
+    ) -> CooperateOrDefect {
+        if (START..END).contains(&my_moves.len()) {
+            Defect
+        } else {
+            Cooperate
+        }
+    }
+}
+
+/// Work out which of Axelrod's traits the strategy `S` has by playing it against an opponent
+/// that always cooperates and one that cooperates apart from two defections in a row.
+///
+/// - It is nice if it never defects first in either game.
+/// - It is retaliatory if, soon after the provocation, it defects in a round in which it
+///   cooperated against the opponent that always cooperates.
+/// - It is forgiving if it is cooperating again by the end of the game with the provocation.
+/// - It is clear if, across both games, it always answers the same outcome of the previous round
+///   with the same move.
+pub fn classify<S>() -> StrategyTraits
+where
+    S: Strategy + 'static,
+    S::State: 'static,
+{
+    let mut control = RepeatedPrisonersDilemma::<S, AlwaysCooperate>::new();
+    control.play_n_rounds(PROBE_ROUNDS);
+    let mut provoked =
+        RepeatedPrisonersDilemma::<S, Provoker<{ PROVOCATION.start }, { PROVOCATION.end }>>::new();
+    provoked.play_n_rounds(PROBE_ROUNDS);
+
+    let defects_first = |game: Option<(Player, usize)>| matches!(game, Some((Player::One, _)));
+    let nice =
+        !defects_first(control.first_defection()) && !defects_first(provoked.first_defection());
+
+    let retaliatory = (PROVOCATION.start + 1..PROVOCATION.end + RETALIATION_WINDOW).any(|round| {
+        matches!(
+            (
+                &provoked.player_1_moves[round],
+                &control.player_1_moves[round]
+            ),
+            (Defect, Cooperate)
+        )
+    });
+
+    let forgiving = matches!(provoked.player_1_moves.last(), Some(Cooperate));
+
+    let mut responses = HashMap::new();
+    let clear = [&control.player_1_moves, &provoked.player_1_moves]
+        .into_iter()
+        .zip([&control.player_2_moves, &provoked.player_2_moves])
+        .all(|(my_moves, their_moves)| {
+            (1..my_moves.len()).all(|round| {
+                let previous = outcome(my_moves[round - 1], their_moves[round - 1]);
+                let response = my_moves[round].as_char();
+                *responses.entry(previous).or_insert(response) == response
+            })
+        });
+
+    StrategyTraits {
+        nice,
+        retaliatory,
+        forgiving,
+        clear,
+    }
+}
+
+/// How well the strategy `S` copes with mistakes against `opponent`. For each of the
+/// `noise_levels`, `trials` games of `NUM_TURNS` rounds are played with that chance of each
+/// move being flipped (see [`RepeatedPrisonersDilemma::set_noise`]), and the result pairs the
+/// noise level with `S`'s average score per round over those games.
+///
+/// Trial `t` uses the same seeds at every noise level, so the results are reproducible. A
+/// trials count of 0 gives an average of 0.
+pub fn noise_robustness<S>(
+    opponent: &Entrant,
+    noise_levels: &[f64],
+    trials: usize,
+) -> Vec<(f64, f64)>
+where
+    S: Strategy + 'static,
+    S::State: 'static,
+{
+    noise_robustness_of(&Entrant::new::<S>(), opponent, noise_levels, trials)
+}
+
+/// The same as [`noise_robustness`], for a strategy that is not a [`Strategy`] type, such as
+/// one that makes random choices.
+pub fn noise_robustness_of(
+    player: &Entrant,
+    opponent: &Entrant,
+    noise_levels: &[f64],
+    trials: usize,
+) -> Vec<(f64, f64)> {
+    noise_levels
+        .iter()
+        .map(|&noise| {
+            let total = (0..trials)
+                .map(|trial| {
+                    let mut rng = Rng::new(trial as u64);
+                    let (p1_seed, p2_seed, noise_seed) =
+                        (rng.next_u64(), rng.next_u64(), rng.next_u64());
+                    let mut game =
+                        DynGame::new(player.new_player(p1_seed), opponent.new_player(p2_seed));
+                    game.set_noise(noise, noise_seed);
+                    game.play_n_rounds(NUM_TURNS);
+                    game.normalized_score().0
+                })
+                .sum::<f64>();
+            let average = if trials == 0 {
+                0.0
+            } else {
+                total / trials as f64
+            };
+            (noise, average)
+        })
+        .collect()
+}
+
+/// How predictable the strategy `S` is against `opponent` over a game of `rounds` rounds, as a
+/// score from 0 to 1. The score is 1 minus the entropy, in bits, of its moves given the outcome
+/// of the previous round, so a strategy whose moves always follow from the last round scores 1
+/// and one that flips a fair coin scores close to 0. A game with no rounds scores 1.
+///
+/// ```
+/// # use prisoners_dilemma_competition::*;
+/// let random = Entrant::seeded("Random", |seed| Box::new(Random::new(0.5, seed).unwrap()));
+/// assert_eq!(predictability::<TitForTat>(&random, 1000), 1.0);
+///
+/// let coin_flips = predictability_of(&random, &Entrant::new::<TitForTat>(), 10_000);
+/// assert!(coin_flips < 0.01, "{coin_flips}");
+/// ```
+pub fn predictability<S>(opponent: &Entrant, rounds: usize) -> f64
+where
+    S: Strategy + 'static,
+    S::State: 'static,
+{
+    predictability_of(&Entrant::new::<S>(), opponent, rounds)
+}
+
+/// The same as [`predictability`], for a strategy that is not a [`Strategy`] type, such as one
+/// that makes random choices. Both players are created with the seed 0.
+pub fn predictability_of(player: &Entrant, opponent: &Entrant, rounds: usize) -> f64 {
+    let mut game = DynGame::new(player.new_player(0), opponent.new_player(0));
+    game.play_n_rounds(rounds);
+
+    // How often the player cooperated and defected after each previous outcome, with `None`
+    // for the first move
+    let mut counts: HashMap<Option<Outcome>, (usize, usize)> = HashMap::new();
+    for round in 0..rounds {
+        let previous = round
+            .checked_sub(1)
+            .map(|last| outcome(game.player_1_moves[last], game.player_2_moves[last]));
+        let count = counts.entry(previous).or_default();
+        match game.player_1_moves[round] {
+            Cooperate => count.0 += 1,
+            Defect => count.1 += 1,
+        }
+    }
+
+    let entropy = counts
+        .values()
+        .map(|&(cooperations, defections)| {
+            let total = (cooperations + defections) as f64;
+            let bits = [cooperations, defections]
+                .into_iter()
+                .filter(|&n| n > 0)
+                .map(|n| {
+                    let p = n as f64 / total;
+                    -p * p.log2()
+                })
+                .sum::<f64>();
+            total / rounds as f64 * bits
+        })
+        .sum::<f64>();
+
+    1.0 - entropy
+}
+
+/// Play the strategy `S` against an independent copy of itself for `NUM_TURNS` rounds. Nice
+/// strategies should settle into mutual cooperation with their own clones.
+pub fn self_play_result<S>() -> GameResult
+where
+    S: Strategy + 'static,
+    S::State: 'static,
+{
+    self_play_result_of(&Entrant::new::<S>())
+}
+
+/// The same as [`self_play_result`], for a strategy that is not a [`Strategy`] type. The two
+/// copies are created with different seeds, so copies of a strategy that makes random choices
+/// do not simply mirror each other.
+pub fn self_play_result_of(entrant: &Entrant) -> GameResult {
+    let mut rng = Rng::new(0);
+    let mut game = DynGame::new(
+        entrant.new_player(rng.next_u64()),
+        entrant.new_player(rng.next_u64()),
+    );
+    game.play_n_rounds(NUM_TURNS);
+    game.result()
+}
+
+/// How a strategy reacted to a single defection, see [`provoke`]. Rounds are counted from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProbeReport {
+    /// The round in which the opponent defected
+    pub provoked_in: usize,
+    /// The first round after the provocation in which the strategy defected, if it ever did
+    pub retaliated_in: Option<usize>,
+    /// How many rounds in a row the strategy defected, starting from `retaliated_in`
+    pub retaliation_length: usize,
+    /// Whether the strategy cooperated again at some point after its retaliation, or after the
+    /// provocation if it never retaliated
+    pub returned_to_cooperation: bool,
+}
+
+/// The round, counting from 0, in which the opponent in [`provoke`] defects
+const SINGLE_PROVOCATION: usize = 5;
+
+/// Play the strategy `S` for `PROBE_ROUNDS` rounds against an opponent that always cooperates
+/// except for a single defection in round 6, and report how `S` responded.
+pub fn provoke<S>() -> ProbeReport
+where
+    S: Strategy + 'static,
+    S::State: 'static,
+{
+    let mut game = RepeatedPrisonersDilemma::<
+        S,
+        Provoker<SINGLE_PROVOCATION, { SINGLE_PROVOCATION + 1 }>,
+    >::new();
+    game.play_n_rounds(PROBE_ROUNDS);
+    let moves = &game.player_1_moves;
+
+    let after = SINGLE_PROVOCATION + 1;
+    let retaliation = moves[after..]
+        .iter()
+        .position(|m| *m == Defect)
+        .map(|i| after + i);
+    let retaliation_length = retaliation.map_or(0, |start| {
+        moves[start..].iter().take_while(|m| **m == Defect).count()
+    });
+    let retaliation_end = retaliation.map_or(after, |start| start + retaliation_length);
+
+    ProbeReport {
+        provoked_in: SINGLE_PROVOCATION + 1,
+        retaliated_in: retaliation.map(|round| round + 1),
+        retaliation_length,
+        returned_to_cooperation: moves[retaliation_end..].contains(&Cooperate),
+    }
+}
+
+/// A Markov chain on the four outcomes, where row `i` holds the chances of moving from the
+/// outcome `Outcome::ALL[i]` to each of the four outcomes
+type Transitions = [[f64; 4]; 4];
+
+/// The long run behavior of a game between the memory-one strategies `a`, as player 1, and
+/// `b`, worked out from their probabilities instead of by playing. A round's outcome depends
+/// only on the one before, so the game is a Markov chain on the four outcomes. This returns how
+/// often each outcome happens in the long run, in the order of [`Outcome::ALL`], and the average
+/// payout per round that `a` and `b` receive under `payoffs`.
+///
+/// Some chains, such as Tit for Tat against itself, have more than one stationary distribution,
+/// and which one a game ends up in depends on how it starts. So this follows the chain from the
+/// players' openings and averages over its first 2^40 rounds, using repeated squaring of the
+/// transition matrix to add up that many rounds in a few dozen steps. The result matches the
+/// infinitely long game to about twelve decimal places.
+///
+/// Long simulated games settle on the same frequencies and payouts:
+///
+/// ```
+/// # use prisoners_dilemma_competition::*;
+/// fn simulate(a: MemoryOne, b: MemoryOne, rounds: usize) -> ([f64; 4], f64, f64) {
+///     let mut game = DynGame::new(Box::new(a), Box::new(b));
+///     game.play_n_rounds(rounds);
+///     let outcomes = game.outcomes();
+///     let frequencies = Outcome::ALL.map(|outcome| {
+///         outcomes.iter().filter(|&&o| o == outcome).count() as f64 / rounds as f64
+///     });
+///     let (a_score, b_score) = game.calculate_score();
+///     (frequencies, a_score as f64 / rounds as f64, b_score as f64 / rounds as f64)
+/// }
+///
+/// let payoffs = PayoffMatrix::default();
+/// let tit_for_tat = |seed| MemoryOne::new(1.0, 0.0, 1.0, 0.0, 1.0, seed).unwrap();
+/// let generous = |seed| MemoryOne::new(1.0, 1.0 / 3.0, 1.0, 1.0 / 3.0, 1.0, seed).unwrap();
+/// let shaky_pavlov = |seed| MemoryOne::new(0.9, 0.1, 0.2, 0.8, 0.5, seed).unwrap();
+///
+/// for (a, b, rounds) in [
+///     (tit_for_tat(1), tit_for_tat(2), 1_000),
+///     (generous(1), shaky_pavlov(2), 200_000),
+/// ] {
+///     let (expected, a_expected, b_expected) = stationary_distribution(&a, &b, &payoffs);
+///     let (simulated, a_simulated, b_simulated) = simulate(a, b, rounds);
+///     for (expected, simulated) in expected.iter().zip(simulated) {
+///         assert!((expected - simulated).abs() < 0.01, "{expected} vs {simulated}");
+///     }
+///     assert!((a_expected - a_simulated).abs() < 0.2, "{a_expected} vs {a_simulated}");
+///     assert!((b_expected - b_simulated).abs() < 0.2, "{b_expected} vs {b_simulated}");
+/// }
+/// ```
+pub fn stationary_distribution(
+    a: &MemoryOne,
+    b: &MemoryOne,
+    payoffs: &PayoffMatrix,
+) -> ([f64; 4], f64, f64) {
+    // The chance each player cooperates after each outcome, named from player 1's point of view
+    // as in `Outcome::ALL`, so player 2's probabilities for CD and DC are swapped
+    let a_cooperates = [a.p_cc, a.p_cd, a.p_dc, a.p_dd];
+    let b_cooperates = [b.p_cc, b.p_dc, b.p_cd, b.p_dd];
+    let next_outcomes =
+        |a: f64, b: f64| [a * b, a * (1.0 - b), (1.0 - a) * b, (1.0 - a) * (1.0 - b)];
+
+    let opening = next_outcomes(a.p_opening, b.p_opening);
+    let transitions: Transitions =
+        std::array::from_fn(|i| next_outcomes(a_cooperates[i], b_cooperates[i]));
+
+    // After each step, `sum` adds up the first 2^k powers of the chain and `power` is the
+    // 2^k-th power
+    const DOUBLINGS: i32 = 40;
+    let mut sum: Transitions =
+        std::array::from_fn(|i| std::array::from_fn(|j| if i == j { 1.0 } else { 0.0 }));
+    let mut power = transitions;
+    for _ in 0..DOUBLINGS {
+        let later = multiply(&sum, &power);
+        sum = std::array::from_fn(|i| std::array::from_fn(|j| sum[i][j] + later[i][j]));
+        power = multiply(&power, &power);
+    }
+
+    let rounds = 2f64.powi(DOUBLINGS);
+    let distribution: [f64; 4] =
+        std::array::from_fn(|j| (0..4).map(|i| opening[i] * sum[i][j]).sum::<f64>() / rounds);
+    let (a_score, b_score) = Outcome::ALL.iter().zip(&distribution).fold(
+        (0.0, 0.0),
+        |(a_score, b_score), (&outcome, &share)| {
+            let (a_payout, b_payout) = payoffs.outcome_payouts(outcome);
+            (
+                a_score + share * a_payout as f64,
+                b_score + share * b_payout as f64,
+            )
+        },
+    );
+    (distribution, a_score, b_score)
+}
+
+fn multiply(x: &Transitions, y: &Transitions) -> Transitions {
+    std::array::from_fn(|i| std::array::from_fn(|j| (0..4).map(|k| x[i][k] * y[k][j]).sum()))
+}
+
+/// Play the strategy `S` against `opponent` for `rounds` rounds and return the fraction of
+/// rounds in which `S` made the same move as the opponent, as a rough measure of how well it
+/// anticipates the opponent. Strategies that settle into mirroring their opponents score highly
+/// too, so this is most telling against opponents whose moves change. With no rounds the
+/// accuracy is 0.
+pub fn prediction_accuracy<S>(opponent: Box<dyn DynStrategy>, rounds: usize) -> f64
+where
+    S: Strategy + 'static,
+    S::State: 'static,
+{
+    let mut game = DynGame::new(StrategyInstance::<S>::boxed(), opponent);
+    game.play_n_rounds(rounds);
+    if rounds == 0 {
+        return 0.0;
+    }
+    let matches = game
+        .player_1_moves()
+        .iter()
+        .zip(game.player_2_moves())
+        .filter(|(mine, theirs)| mine == theirs)
+        .count();
+    matches as f64 / rounds as f64
+}
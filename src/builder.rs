@@ -0,0 +1,93 @@
+//! A more readable way to set up a game than calling each setter in turn.
+
+use crate::{
+    DynGame, DynStrategy, GameResult, PayoffMatrix, Strategy, StrategyInstance, NUM_TURNS,
+};
+
+/// Settings for a single game, set with chainable methods. Anything that is not set keeps its
+/// default: `NUM_TURNS` rounds, the default payoffs, no noise, and a seed of 0.
+///
+/// ```
+/// # use prisoners_dilemma_competition::*;
+/// let result = GameBuilder::new()
+///     .rounds(50)
+///     .noise(0.05)
+///     .seed(7)
+///     .play::<TitForTat, Pavlov>();
+/// assert_eq!(result.rounds, 50);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GameBuilder {
+    rounds: usize,
+    payoffs: PayoffMatrix,
+    noise: f64,
+    seed: u64,
+}
+
+impl Default for GameBuilder {
+    fn default() -> Self {
+        Self {
+            rounds: NUM_TURNS,
+            payoffs: PayoffMatrix::default(),
+            noise: 0.0,
+            seed: 0,
+        }
+    }
+}
+
+impl GameBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of rounds to play.
+    pub fn rounds(mut self, rounds: usize) -> Self {
+        self.rounds = rounds;
+        self
+    }
+
+    /// The payoffs to score the game with.
+    pub fn payoffs(mut self, payoffs: PayoffMatrix) -> Self {
+        self.payoffs = payoffs;
+        self
+    }
+
+    /// The chance that each move is flipped by mistake, see
+    /// [`RepeatedPrisonersDilemma::set_noise`](crate::RepeatedPrisonersDilemma::set_noise).
+    pub fn noise(mut self, noise: f64) -> Self {
+        self.noise = noise;
+        self
+    }
+
+    /// The seed that decides which moves the noise flips.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Play a game between the strategies `P1` and `P2` with these settings.
+    pub fn play<P1, P2>(&self) -> GameResult
+    where
+        P1: Strategy + 'static,
+        P2: Strategy + 'static,
+        P1::State: 'static,
+        P2::State: 'static,
+    {
+        self.play_dyn(
+            StrategyInstance::<P1>::boxed(),
+            StrategyInstance::<P2>::boxed(),
+        )
+    }
+
+    /// Play a game between two players chosen at runtime with these settings.
+    pub fn play_dyn(
+        &self,
+        player_1: Box<dyn DynStrategy>,
+        player_2: Box<dyn DynStrategy>,
+    ) -> GameResult {
+        let mut game = DynGame::with_payoffs(player_1, player_2, self.payoffs);
+        game.set_noise(self.noise, self.seed);
+        game.play_n_rounds(self.rounds);
+        game.result()
+    }
+}
@@ -0,0 +1,305 @@
+//! Command line arguments for playing a single game between two named strategies, or a
+//! tournament between all of them.
+//!
+//! The crate has no dependencies, so the arguments are parsed by hand rather than with clap.
+
+use std::{error::Error, fmt};
+
+use crate::{
+    default_entrants, format_annotated_leaderboard, format_score_matrix,
+    run_tournament_with_config, tournament_results_with_config, DynStrategy, FsmStrategy,
+    PayoffPreset, TournamentConfig, UnknownPreset,
+};
+
+/// The usage message printed by `--help` and after an invalid argument.
+pub const USAGE: &str = "\
+Usage: prisoners [--p1 <STRATEGY> --p2 <STRATEGY>] [OPTIONS]
+       prisoners tournament [--rounds <N>] [--noise <P>] [--seed <N>] [--double-round-robin] [--matrix]
+       prisoners play --against <STRATEGY> [--rounds <N>] [--config <PATH>] [--payoffs <NAME>]
+
+With no strategies, plays a round-robin tournament between every built-in strategy. The
+`tournament` command does the same with the given settings. The `play` command lets you play
+against a strategy yourself, typing C or D each round.
+
+Options:
+  --p1 <STRATEGY>   Strategy for player 1, for example tit-for-tat, or a .strat file
+  --p2 <STRATEGY>   Strategy for player 2, for example always-defect, or a .strat file
+  --against <STRATEGY>
+                    With `play`, the strategy to play against
+  --config <PATH>   TOML file with the rounds, noise, and payoffs to use, except in a
+                    tournament
+  --payoffs <NAME>  Payoff preset to use, except in a tournament, overriding the config
+                    file: default, axelrod, snowdrift, or harsh-punishment
+  --rounds <N>      Number of rounds to play, overriding the config file
+  --noise <P>       Chance that each move is flipped by mistake, overriding the config file
+  --seed <N>        Seed for the noise
+  --double-round-robin
+                    In a tournament, play each pair twice, once in each position
+  --matrix          After a tournament, also print every strategy's score against every other
+  --list            List the built-in strategies and what they do
+  -h, --help        Print this message";
+
+/// The settings given on the command line. Anything that was not given is `None`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CliArgs {
+    pub p1: Option<String>,
+    pub p2: Option<String>,
+    /// The opponent for the `play` command
+    pub against: Option<String>,
+    pub config: Option<String>,
+    pub rounds: Option<usize>,
+    pub noise: Option<f64>,
+    pub seed: Option<u64>,
+    pub payoffs: Option<PayoffPreset>,
+    /// Whether the `tournament` command was given
+    pub tournament: bool,
+    /// Whether the `play` command was given
+    pub play: bool,
+    pub double_round_robin: bool,
+    pub matrix: bool,
+    pub list: bool,
+    pub help: bool,
+}
+
+/// Why the command line arguments could not be understood.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliError(pub String);
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for CliError {}
+
+impl CliArgs {
+    /// Parse the arguments, not including the program name. Values can be given either as the
+    /// next argument (`--rounds 500`) or after an equals sign (`--rounds=500`).
+    ///
+    /// ```
+    /// # use prisoners_dilemma_competition::{CliArgs, PayoffPreset};
+    /// let args = CliArgs::parse([
+    ///     "--p1", "tit-for-tat", "--p2", "always-defect", "--rounds", "500", "--noise=0.05",
+    /// ])?;
+    /// assert_eq!(
+    ///     args,
+    ///     CliArgs {
+    ///         p1: Some("tit-for-tat".into()),
+    ///         p2: Some("always-defect".into()),
+    ///         rounds: Some(500),
+    ///         noise: Some(0.05),
+    ///         ..CliArgs::default()
+    ///     }
+    /// );
+    ///
+    /// for invalid in [
+    ///     &["--p1", "tit-for-tat"][..],
+    ///     &["--rounds", "many"],
+    ///     &["--noise", "2"],
+    ///     &["--colour"],
+    ///     &["--rounds"],
+    ///     &["tournament", "--p1", "pavlov", "--p2", "pavlov"],
+    ///     &["tournament", "--payoffs", "snowdrift"],
+    ///     &["--payoffs", "snowdrift", "--rounds", "5"],
+    ///     &["--config", "settings.toml"],
+    ///     &["play"],
+    ///     &["--against", "pavlov"],
+    ///     &["--matrix"],
+    ///     &["play", "--against", "pavlov", "--noise", "0.5", "--seed", "3"],
+    ///     &["play", "--against", "pavlov", "--seed", "3"],
+    /// ] {
+    ///     assert!(CliArgs::parse(invalid.iter().copied()).is_err(), "{invalid:?}");
+    /// }
+    ///
+    /// let game = CliArgs::parse(["--p1", "pavlov", "--p2", "joss", "--payoffs", "snowdrift"])?;
+    /// assert_eq!(game.payoffs, Some(PayoffPreset::Snowdrift));
+    /// # Ok::<(), prisoners_dilemma_competition::CliError>(())
+    /// ```
+    pub fn parse<I>(args: I) -> Result<Self, CliError>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter().map(Into::into);
+
+        while let Some(arg) = args.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+
+            if flag == "-h" || flag == "--help" {
+                parsed.help = true;
+                continue;
+            }
+            if flag == "--list" {
+                parsed.list = true;
+                continue;
+            }
+            if flag == "--double-round-robin" {
+                parsed.double_round_robin = true;
+                continue;
+            }
+            if flag == "--matrix" {
+                parsed.matrix = true;
+                continue;
+            }
+            if flag == "tournament" {
+                parsed.tournament = true;
+                continue;
+            }
+            if flag == "play" {
+                parsed.play = true;
+                continue;
+            }
+
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| CliError(format!("`{flag}` needs a value")))
+            };
+            match flag.as_str() {
+                "--p1" => parsed.p1 = Some(value()?),
+                "--p2" => parsed.p2 = Some(value()?),
+                "--against" => parsed.against = Some(value()?),
+                "--config" => parsed.config = Some(value()?),
+                "--rounds" => parsed.rounds = Some(parse_value(&flag, &value()?)?),
+                "--noise" => {
+                    let noise = parse_value(&flag, &value()?)?;
+                    if !(0.0..=1.0).contains(&noise) {
+                        return Err(CliError(format!(
+                            "`--noise` must be between 0 and 1, found {noise}"
+                        )));
+                    }
+                    parsed.noise = Some(noise);
+                }
+                "--seed" => parsed.seed = Some(parse_value(&flag, &value()?)?),
+                "--payoffs" => {
+                    let preset = value()?
+                        .parse()
+                        .map_err(|e: UnknownPreset| CliError(e.to_string()))?;
+                    parsed.payoffs = Some(preset);
+                }
+                _ => return Err(CliError(format!("unknown argument `{flag}`"))),
+            }
+        }
+
+        if parsed.p1.is_some() != parsed.p2.is_some() {
+            return Err(CliError("`--p1` and `--p2` must be given together".into()));
+        }
+        if parsed.tournament && parsed.p1.is_some() {
+            return Err(CliError(
+                "`tournament` plays every strategy, so it takes no `--p1` or `--p2`".into(),
+            ));
+        }
+        // Without `--p1` and `--p2`, or `play`, a tournament is played even if `tournament` was
+        // not typed, and tournaments always use the default payoffs.
+        let plays_tournament = parsed.tournament || (parsed.p1.is_none() && !parsed.play);
+        if plays_tournament
+            && !(parsed.help || parsed.list)
+            && (parsed.config.is_some() || parsed.payoffs.is_some())
+        {
+            return Err(CliError(
+                "a tournament plays every strategy with the default payoffs, so it takes no \
+                 `--config` or `--payoffs`"
+                    .into(),
+            ));
+        }
+        if parsed.play && (parsed.tournament || parsed.p1.is_some()) {
+            return Err(CliError(
+                "`play` is a game between you and the `--against` strategy, so it cannot be \
+                 combined with `tournament`, `--p1`, or `--p2`"
+                    .into(),
+            ));
+        }
+        if parsed.play && (parsed.noise.is_some() || parsed.seed.is_some()) {
+            return Err(CliError(
+                "`play` is played without noise, so it takes no `--noise` or `--seed`".into(),
+            ));
+        }
+        if parsed.play != parsed.against.is_some() {
+            return Err(CliError(
+                "`play` and `--against` must be given together".into(),
+            ));
+        }
+        if !parsed.tournament && (parsed.double_round_robin || parsed.matrix) {
+            return Err(CliError(
+                "`--double-round-robin` and `--matrix` can only be used with `tournament`".into(),
+            ));
+        }
+
+        Ok(parsed)
+    }
+
+    /// The tournament settings given on the command line, with defaults for the rest.
+    pub fn tournament_config(&self) -> TournamentConfig {
+        let defaults = TournamentConfig::default();
+        TournamentConfig {
+            seed: self.seed.unwrap_or(defaults.seed),
+            rounds: self.rounds.unwrap_or(defaults.rounds),
+            noise: self.noise.unwrap_or(defaults.noise),
+            double_round_robin: self.double_round_robin,
+            ..defaults
+        }
+    }
+}
+
+/// The player given by a `--p1` or `--p2` value: the finite state machine read from the file if
+/// the value ends in `.strat`, and otherwise the built-in strategy with that name. An unknown
+/// name is an error that lists the strategies there are:
+///
+/// ```
+/// # use prisoners_dilemma_competition::player_from_arg;
+/// assert_eq!(player_from_arg("tit-for-tat").unwrap().name(), "Tit for Tat");
+///
+/// let error = player_from_arg("tit-for-that").err().unwrap().to_string();
+/// assert!(error.contains("tit-for-that"));
+/// assert!(error.contains("Always Defect"));
+/// ```
+pub fn player_from_arg(value: &str) -> Result<Box<dyn DynStrategy>, Box<dyn Error>> {
+    if value.ends_with(".strat") {
+        let machine = FsmStrategy::load(value).map_err(|e| format!("{value}: {e}"))?;
+        Ok(Box::new(machine))
+    } else {
+        Ok(value.parse()?)
+    }
+}
+
+/// Run the `tournament` command: play every built-in strategy against every other with the
+/// settings in `args`, and return the leaderboard, followed by the score matrix if `--matrix`
+/// was given.
+///
+/// ```
+/// # use prisoners_dilemma_competition::{available_strategies, tournament_command, CliArgs};
+/// let args = CliArgs::parse(["tournament", "--rounds", "20", "--double-round-robin"])?;
+/// let output = tournament_command(&args);
+/// for name in available_strategies() {
+///     assert!(output.lines().any(|line| line.contains(name)), "{name} is missing");
+/// }
+/// # Ok::<(), prisoners_dilemma_competition::CliError>(())
+/// ```
+pub fn tournament_command(args: &CliArgs) -> String {
+    let entrants = default_entrants();
+    let config = args.tournament_config();
+
+    let mut output = format!(
+        "Playing a round-robin tournament between {} strategies\n",
+        entrants.len()
+    );
+    output +=
+        &format_annotated_leaderboard(&run_tournament_with_config(&entrants, &config), &entrants);
+    if args.matrix {
+        output += "\n";
+        output += &format_score_matrix(&tournament_results_with_config(&entrants, &config));
+    }
+    output
+}
+
+fn parse_value<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, CliError> {
+    value
+        .parse()
+        .map_err(|_| CliError(format!("invalid value `{value}` for `{flag}`")))
+}
@@ -0,0 +1,190 @@
+//! Game settings read from a TOML file, such as
+//!
+//! ```toml
+//! rounds = 500
+//! noise = 0.05
+//!
+//! [payoffs]
+//! reward = 3
+//! temptation = 5
+//! punishment = 1
+//! sucker = 0
+//! ```
+//!
+//! Instead of giving all four payoffs, the `[payoffs]` table can name a
+//! [`PayoffPreset`](crate::PayoffPreset) with a line such as `preset = "axelrod"`. Any payoffs
+//! given after it replace the preset's.
+//!
+//! The crate has no dependencies, so rather than using serde and the toml crate this module
+//! understands just the part of TOML needed for these settings: comments, `[table]` headers, and
+//! `key = value` lines whose values are numbers or, for the preset, a string.
+
+use std::{fmt, fs, path::Path};
+
+use crate::{CliArgs, PayoffMatrix, NUM_TURNS};
+
+/// The settings for a game. Anything missing from a config file keeps its default value: the
+/// crate's constants for the rounds and payoffs, and no noise.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GameConfig {
+    pub rounds: usize,
+    pub noise: f64,
+    pub payoffs: PayoffMatrix,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            rounds: NUM_TURNS,
+            noise: 0.0,
+            payoffs: PayoffMatrix::default(),
+        }
+    }
+}
+
+/// Why a config file could not be used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigError(pub String);
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid config: {}", self.0)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl GameConfig {
+    /// Read the settings from a TOML file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .map_err(|e| ConfigError(format!("could not read {}: {e}", path.display())))?;
+        Self::from_toml(&text)
+    }
+
+    /// Read the settings from the text of a TOML file.
+    pub fn from_toml(text: &str) -> Result<Self, ConfigError> {
+        let mut config = Self::default();
+        let mut table = String::new();
+
+        for (number, line) in text.lines().enumerate() {
+            let error = |message: String| ConfigError(format!("line {}: {message}", number + 1));
+
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('[') {
+                let name = header
+                    .strip_suffix(']')
+                    .ok_or_else(|| error("expected `]` after the table name".into()))?;
+                table = name.trim().to_string();
+                if table != "payoffs" {
+                    return Err(error(format!("unknown table `[{table}]`")));
+                }
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| error("expected `key = value`".into()))?;
+            let (key, value) = (key.trim(), value.trim());
+
+            let payoffs = &mut config.payoffs;
+            match (table.as_str(), key) {
+                ("", "rounds") => config.rounds = parse_value(key, value).map_err(error)?,
+                ("", "noise") => {
+                    config.noise = parse_value(key, value).map_err(error)?;
+                    if !(0.0..=1.0).contains(&config.noise) {
+                        return Err(error("`noise` must be between 0 and 1".into()));
+                    }
+                }
+                ("payoffs", "reward") => payoffs.reward = parse_value(key, value).map_err(error)?,
+                ("payoffs", "temptation") => {
+                    payoffs.temptation = parse_value(key, value).map_err(error)?
+                }
+                ("payoffs", "punishment") => {
+                    payoffs.punishment = parse_value(key, value).map_err(error)?
+                }
+                ("payoffs", "sucker") => payoffs.sucker = parse_value(key, value).map_err(error)?,
+                ("payoffs", "preset") => {
+                    let name = value
+                        .strip_prefix('"')
+                        .and_then(|value| value.strip_suffix('"'))
+                        .ok_or_else(|| {
+                            error(format!("`preset` must be a string, found `{value}`"))
+                        })?;
+                    *payoffs = PayoffMatrix::preset(name).map_err(|e| error(e.to_string()))?;
+                }
+                _ => return Err(error(format!("unknown key `{key}`"))),
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Replace any settings that were also given on the command line, which take priority over
+    /// the config file.
+    pub fn override_with(&mut self, args: &CliArgs) {
+        if let Some(rounds) = args.rounds {
+            self.rounds = rounds;
+        }
+        if let Some(noise) = args.noise {
+            self.noise = noise;
+        }
+        if let Some(preset) = args.payoffs {
+            self.payoffs = preset.payoffs();
+        }
+    }
+
+    /// A key identifying the game that these settings give between the strategies named `p1`
+    /// and `p2` when the noise is seeded with `seed`, for caching results. Games with the same
+    /// settings, seed, and players always get the same key, on every platform and in every
+    /// version of Rust, and changing any of them gives a different key except in the rare case
+    /// of a collision.
+    pub fn id(&self, seed: u64, p1: &str, p2: &str) -> u64 {
+        let PayoffMatrix {
+            reward,
+            temptation,
+            punishment,
+            sucker,
+        } = self.payoffs;
+        // Adding zero turns a noise of -0.0 into 0.0, which plays the same games
+        let numbers = [
+            self.rounds as u64,
+            (self.noise + 0.0).to_bits(),
+            reward as u64,
+            temptation as u64,
+            punishment as u64,
+            sucker as u64,
+            seed,
+        ];
+
+        // 64-bit FNV-1a, since the standard library's hashers are free to change between
+        // versions. The names are prefixed with their lengths so that moving characters from
+        // one name to the other changes the key.
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let bytes = numbers
+            .into_iter()
+            .chain([p1.len() as u64])
+            .flat_map(|number| number.to_le_bytes())
+            .chain(p1.bytes())
+            .chain((p2.len() as u64).to_le_bytes())
+            .chain(p2.bytes());
+        for byte in bytes {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+        hash
+    }
+}
+
+/// Parse a TOML number, which may use underscores between digits.
+fn parse_value<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value
+        .replace('_', "")
+        .parse()
+        .map_err(|_| format!("invalid value `{value}` for `{key}`"))
+}
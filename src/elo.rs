@@ -0,0 +1,83 @@
+//! Elo ratings, for ranking strategies by who they beat rather than by how many points they
+//! piled up.
+
+use std::collections::HashMap;
+
+use crate::{score, AsymmetricPayoffs, GameRecord, PayoffMatrix};
+
+/// The rating every strategy starts with.
+pub const INITIAL_RATING: f64 = 1500.0;
+
+/// The K-factor used by [`elo_ratings`]. Larger values make each game move the ratings further.
+pub const DEFAULT_K_FACTOR: f64 = 32.0;
+
+/// Rate every strategy that appears in `games` using [`DEFAULT_K_FACTOR`] and the default payoffs.
+///
+/// Always Defect wins every one of its games, so it ends up rated highest:
+///
+/// ```
+/// # use prisoners_dilemma_competition::*;
+/// let game = |p1: &str, p2: &str| {
+///     let mut game = DynGame::new(create_strategy(p1).unwrap(), create_strategy(p2).unwrap());
+///     game.play_n_rounds(10);
+///     game.into_record()
+/// };
+///
+/// let games = [
+///     game("Always Defect", "Tit for Tat"),
+///     game("Tit for Tat", "Always Cooperate"),
+///     game("Always Cooperate", "Always Defect"),
+///     game("Tit for Tat", "Always Defect"),
+/// ];
+/// let ratings = elo_ratings(&games);
+/// assert!(ratings["Always Defect"] > INITIAL_RATING);
+/// assert!(ratings["Always Defect"] > ratings["Tit for Tat"]);
+/// assert!(ratings["Always Defect"] > ratings["Always Cooperate"]);
+/// ```
+pub fn elo_ratings(games: &[GameRecord]) -> HashMap<String, f64> {
+    elo_ratings_with_k_factor(games, &PayoffMatrix::default(), DEFAULT_K_FACTOR)
+}
+
+/// Rate every strategy that appears in `games`, scoring each game with `payoffs`.
+///
+/// Each game is a match that the player with the higher final score wins, or a draw if the
+/// scores are equal. Everyone starts at [`INITIAL_RATING`] and the ratings are updated after
+/// every match, one game at a time in the order the games appear in the slice. The result
+/// therefore depends on that order. Games between two players with the same name are skipped.
+pub fn elo_ratings_with_k_factor(
+    games: &[GameRecord],
+    payoffs: &PayoffMatrix,
+    k_factor: f64,
+) -> HashMap<String, f64> {
+    let mut ratings = HashMap::new();
+
+    for game in games {
+        let p1_rating = *ratings
+            .entry(game.p1_name.clone())
+            .or_insert(INITIAL_RATING);
+        let p2_rating = *ratings
+            .entry(game.p2_name.clone())
+            .or_insert(INITIAL_RATING);
+        if game.p1_name == game.p2_name {
+            continue;
+        }
+
+        let (p1_score, p2_score) = score(
+            &AsymmetricPayoffs::symmetric(*payoffs),
+            &game.p1_moves,
+            &game.p2_moves,
+        );
+        let p1_result = match p1_score.cmp(&p2_score) {
+            std::cmp::Ordering::Greater => 1.0,
+            std::cmp::Ordering::Equal => 0.5,
+            std::cmp::Ordering::Less => 0.0,
+        };
+        let p1_expected = 1.0 / (1.0 + 10f64.powf((p2_rating - p1_rating) / 400.0));
+        let change = k_factor * (p1_result - p1_expected);
+
+        ratings.insert(game.p1_name.clone(), p1_rating + change);
+        ratings.insert(game.p2_name.clone(), p2_rating - change);
+    }
+
+    ratings
+}
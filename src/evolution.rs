@@ -0,0 +1,501 @@
+//! Simulations of how a population of strategies changes over many generations, when strategies
+//! that score well become more common.
+
+use std::collections::HashMap;
+
+use crate::{tournament::play_game, Entrant, Json, Rng, NUM_TURNS};
+
+/// Simulate discrete replicator dynamics. `population` maps strategy names to the fraction of
+/// the population following that strategy. In each generation, every strategy's fitness is the
+/// average score per round it earns against the current population mix, and its share grows or
+/// shrinks in proportion to its fitness relative to the population average.
+///
+/// The names must belong to strategies in `entrants`, which are used to play the games. Each pair
+/// of strategies (including each strategy against itself) plays one game of `NUM_TURNS` rounds
+/// up front. Shares cannot become negative, so if any score in that table is negative, every
+/// score is shifted up just far enough that the worst one counts as zero fitness. If the whole
+/// population has zero fitness in some generation, the shares stay as they were.
+///
+/// The result starts with the initial population, normalized so that it sums to 1, followed by
+/// one entry per generation. Every entry sums to 1, so a population of strategies that all
+/// score the same, or of a single strategy, never changes:
+///
+/// ```
+/// # use std::collections::HashMap;
+/// # use prisoners_dilemma_competition::*;
+/// let entrants = [Entrant::new::<TitForTat>(), Entrant::new::<AlwaysCooperate>()];
+///
+/// let alone = replicator_dynamics(&entrants, HashMap::from([("Tit for Tat", 1.0)]), 3);
+/// assert!(alone.iter().all(|shares| shares["Tit for Tat"] == 1.0));
+///
+/// let even = HashMap::from([("Tit for Tat", 0.5), ("Always Cooperate", 0.5)]);
+/// for shares in replicator_dynamics(&entrants, even.clone(), 3) {
+///     assert_eq!(shares, even);
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics if a name in `population` is not the name of one of the `entrants`.
+pub fn replicator_dynamics<'a>(
+    entrants: &[Entrant],
+    population: HashMap<&'a str, f64>,
+    generations: usize,
+) -> Vec<HashMap<&'a str, f64>> {
+    let (names, mut shares): (Vec<&'a str>, Vec<f64>) = population.into_iter().unzip();
+    let players = names
+        .iter()
+        .map(|name| {
+            entrants
+                .iter()
+                .find(|e| e.name() == *name)
+                .unwrap_or_else(|| panic!("no entrant is named {name:?}"))
+        })
+        .collect::<Vec<_>>();
+
+    let payoffs = payoff_table(&players);
+    normalize(&mut shares);
+
+    let mut history = vec![names.iter().copied().zip(shares.iter().copied()).collect()];
+    for _ in 0..generations {
+        let fitness = payoffs
+            .iter()
+            .map(|row| row.iter().zip(&shares).map(|(p, x)| p * x).sum::<f64>())
+            .collect::<Vec<_>>();
+
+        let mut next = shares
+            .iter()
+            .zip(&fitness)
+            .map(|(share, fitness)| share * fitness)
+            .collect::<Vec<_>>();
+        if next.iter().sum::<f64>() > 0.0 {
+            normalize(&mut next);
+            shares = next;
+        }
+
+        history.push(names.iter().copied().zip(shares.iter().copied()).collect());
+    }
+
+    history
+}
+
+/// Export the shares from [`replicator_dynamics`] as CSV, for plotting or animating how the
+/// population changes. There is one row per entry of `history`, so the initial population is
+/// generation 0, and one column per strategy after the `generation` column, in alphabetical
+/// order. A strategy missing from an entry is written as a share of 0.
+pub fn population_csv(history: &[HashMap<&str, f64>]) -> String {
+    let names = strategy_names(history);
+    let mut csv = String::from("generation");
+    for name in &names {
+        csv += &format!(",{}", csv_field(name));
+    }
+    csv.push('\n');
+
+    for (generation, shares) in history.iter().enumerate() {
+        csv += &generation.to_string();
+        for name in &names {
+            csv += &format!(",{}", shares.get(name).copied().unwrap_or_default());
+        }
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Export the shares from [`replicator_dynamics`] as a JSON array with one object per entry of
+/// `history`, holding its `generation` number and its `shares` keyed by strategy name, in
+/// alphabetical order.
+pub fn population_json(history: &[HashMap<&str, f64>]) -> Json {
+    let names = strategy_names(history);
+    Json::Array(
+        history
+            .iter()
+            .enumerate()
+            .map(|(generation, shares)| {
+                let shares = names
+                    .iter()
+                    .map(|&name| {
+                        let share = shares.get(name).copied().unwrap_or_default();
+                        (name.to_string(), Json::Float(share))
+                    })
+                    .collect();
+                Json::Object(vec![
+                    ("generation".into(), Json::Integer(generation as i128)),
+                    ("shares".into(), Json::Object(shares)),
+                ])
+            })
+            .collect(),
+    )
+}
+
+/// Every strategy named anywhere in `history`, in alphabetical order.
+fn strategy_names<'a>(history: &[HashMap<&'a str, f64>]) -> Vec<&'a str> {
+    let mut names = history
+        .iter()
+        .flat_map(|shares| shares.keys().copied())
+        .collect::<Vec<_>>();
+    names.sort_unstable();
+    names.dedup();
+    names
+}
+
+/// Quote a CSV field if it contains anything that would otherwise break the row.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// The average score per round that each player earns against each other player, shifted up so
+/// that the lowest entry is zero if any entry is negative.
+fn payoff_table(players: &[&Entrant]) -> Vec<Vec<f64>> {
+    let mut table = vec![vec![0.0; players.len()]; players.len()];
+    for i in 0..players.len() {
+        for j in i..players.len() {
+            let (i_score, j_score) = play_game(players[i], players[j]);
+            table[i][j] = i_score as f64 / NUM_TURNS as f64;
+            table[j][i] = j_score as f64 / NUM_TURNS as f64;
+        }
+    }
+
+    let lowest = table.iter().flatten().copied().fold(0.0, f64::min);
+    for payoff in table.iter_mut().flatten() {
+        *payoff -= lowest;
+    }
+    table
+}
+
+/// Scale the shares so they sum to 1, leaving them alone if they are all zero.
+fn normalize(shares: &mut [f64]) {
+    let total: f64 = shares.iter().sum();
+    if total > 0.0 {
+        for share in shares {
+            *share /= total;
+        }
+    }
+}
+
+/// A spatial tournament on a grid that wraps around at the edges. Each cell holds one of the
+/// grid's entrants and only ever plays against its eight neighbours.
+pub struct Grid {
+    width: usize,
+    height: usize,
+    entrants: Vec<Entrant>,
+    /// Which entrant occupies each cell, row by row
+    cells: Vec<usize>,
+    /// `scores[i][j]` is what entrant `i` scores in a game against entrant `j`
+    scores: Vec<Vec<isize>>,
+}
+
+impl Grid {
+    /// Create a grid in which every cell holds the first of the `entrants`.
+    ///
+    /// Every pair of entrants plays one game of `NUM_TURNS` rounds up front, and those scores are
+    /// reused whenever the two meet on the grid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no entrants or the grid has no cells.
+    pub fn new(width: usize, height: usize, entrants: Vec<Entrant>) -> Self {
+        assert!(!entrants.is_empty(), "a grid needs at least one entrant");
+        assert!(width > 0 && height > 0, "a grid needs at least one cell");
+
+        let mut scores = vec![vec![0; entrants.len()]; entrants.len()];
+        for i in 0..entrants.len() {
+            for j in i..entrants.len() {
+                let (i_score, j_score) = play_game(&entrants[i], &entrants[j]);
+                scores[i][j] = i_score;
+                scores[j][i] = j_score;
+            }
+        }
+
+        Self {
+            width,
+            height,
+            entrants,
+            cells: vec![0; width * height],
+            scores,
+        }
+    }
+
+    /// The index of the entrant in the cell at column `x` and row `y`.
+    pub fn get(&self, x: usize, y: usize) -> usize {
+        self.cells[y * self.width + x]
+    }
+
+    /// Put the entrant with the given index into the cell at column `x` and row `y`.
+    pub fn set(&mut self, x: usize, y: usize, entrant: usize) {
+        assert!(entrant < self.entrants.len(), "no entrant {entrant}");
+        self.cells[y * self.width + x] = entrant;
+    }
+
+    /// Play one generation. Every cell plays against each of its eight neighbours and totals up
+    /// its score, then every cell switches to the strategy of the highest scoring cell among
+    /// itself and its neighbours. A cell keeps its own strategy when it is tied for the top.
+    ///
+    /// A grid of one strategy stays as it is, while a single defector among cooperators
+    /// outscores all of its neighbours and converts them:
+    ///
+    /// ```
+    /// # use prisoners_dilemma_competition::*;
+    /// let entrants = || vec![Entrant::new::<AlwaysCooperate>(), Entrant::new::<AlwaysDefect>()];
+    /// let mut uniform = Grid::new(5, 5, entrants());
+    /// uniform.step();
+    /// assert_eq!(uniform.render(), "00000\n".repeat(5));
+    ///
+    /// let mut invaded = Grid::new(5, 5, entrants());
+    /// invaded.set(2, 2, 1);
+    /// invaded.step();
+    /// assert_eq!(invaded.render(), "00000\n01110\n01110\n01110\n00000\n");
+    /// ```
+    pub fn step(&mut self) {
+        let totals = (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let me = self.get(x, y);
+                self.neighbours(x, y)
+                    .map(|(nx, ny)| self.scores[me][self.get(nx, ny)])
+                    .fold(0isize, isize::saturating_add)
+            })
+            .collect::<Vec<_>>();
+
+        let mut next = self.cells.clone();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let mut best = y * self.width + x;
+                for (nx, ny) in self.neighbours(x, y) {
+                    let neighbour = ny * self.width + nx;
+                    if totals[neighbour] > totals[best] {
+                        best = neighbour;
+                    }
+                }
+                next[y * self.width + x] = self.cells[best];
+            }
+        }
+        self.cells = next;
+    }
+
+    /// Draw the grid with one character per cell and one line per row. Each cell shows the index
+    /// of its entrant as a base 36 digit (`0`-`9`, then `a`-`z`), or `?` past that.
+    pub fn render(&self) -> String {
+        let mut map = String::with_capacity((self.width + 1) * self.height);
+        for row in self.cells.chunks(self.width) {
+            for &entrant in row {
+                map.push(char::from_digit(entrant as u32, 36).unwrap_or('?'));
+            }
+            map.push('\n');
+        }
+        map
+    }
+
+    /// The coordinates of the eight cells around the given one, wrapping around the edges.
+    fn neighbours(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        [-1isize, 0, 1]
+            .into_iter()
+            .flat_map(|dy| [-1isize, 0, 1].into_iter().map(move |dx| (dx, dy)))
+            .filter(|&offset| offset != (0, 0))
+            .map(move |(dx, dy)| {
+                (
+                    (x as isize + dx).rem_euclid(self.width as isize) as usize,
+                    (y as isize + dy).rem_euclid(self.height as isize) as usize,
+                )
+            })
+    }
+}
+
+/// A finite population of individuals, each following one strategy, that evolves by the Moran
+/// process: one individual reproduces and another dies in each step, so the size never changes.
+///
+/// ```
+/// # use prisoners_dilemma_competition::*;
+/// let entrants = [Entrant::new::<AlwaysDefect>(), Entrant::new::<AlwaysCooperate>()];
+/// let counts = [("Always Defect", 5), ("Always Cooperate", 5)];
+/// let mut population = Population::new(&entrants, &counts, 3);
+/// for _ in 0..10 {
+///     let before = population.counts()["Always Defect"];
+///     population.moran_step();
+///     let after = population.counts()["Always Defect"];
+///     assert!(before.abs_diff(after) <= 1);
+///     assert_eq!(population.counts().values().sum::<usize>(), 10);
+/// }
+///
+/// let winner = population.run_to_fixation();
+/// assert_eq!(population.fixated(), Some(winner));
+/// assert_eq!(population.counts()[winner], 10);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Population<'a> {
+    names: Vec<&'a str>,
+    /// The shifted average score per round of each strategy against each other strategy
+    payoffs: Vec<Vec<f64>>,
+    /// The index into `names` of the strategy each individual follows
+    individuals: Vec<usize>,
+    rng: Rng,
+}
+
+impl<'a> Population<'a> {
+    /// Create a population with `counts` giving how many individuals follow each strategy. The
+    /// names must belong to strategies in `entrants`, which are used to play the games, and each
+    /// pair of strategies plays one game of `NUM_TURNS` rounds up front. As with
+    /// [`replicator_dynamics`], negative scores are shifted up so the worst score counts as zero
+    /// fitness.
+    ///
+    /// The random choices in each step are made by a generator seeded with `seed`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a name is not the name of one of the `entrants`, or if the population has fewer
+    /// than two individuals.
+    pub fn new(entrants: &[Entrant], counts: &[(&'a str, usize)], seed: u64) -> Self {
+        let names = counts.iter().map(|&(name, _)| name).collect::<Vec<_>>();
+        let players = names
+            .iter()
+            .map(|name| {
+                entrants
+                    .iter()
+                    .find(|e| e.name() == *name)
+                    .unwrap_or_else(|| panic!("no entrant is named {name:?}"))
+            })
+            .collect::<Vec<_>>();
+        let individuals = counts
+            .iter()
+            .enumerate()
+            .flat_map(|(strategy, &(_, count))| std::iter::repeat_n(strategy, count))
+            .collect::<Vec<_>>();
+        assert!(
+            individuals.len() >= 2,
+            "a population needs at least two individuals"
+        );
+
+        Self {
+            names,
+            payoffs: payoff_table(&players),
+            individuals,
+            rng: Rng::new(seed),
+        }
+    }
+
+    /// How many individuals follow each strategy.
+    pub fn counts(&self) -> HashMap<&'a str, usize> {
+        let mut counts = self
+            .names
+            .iter()
+            .map(|&name| (name, 0))
+            .collect::<HashMap<_, _>>();
+        for &strategy in &self.individuals {
+            *counts.get_mut(self.names[strategy]).unwrap() += 1;
+        }
+        counts
+    }
+
+    /// The strategy that every individual follows, if there is only one left.
+    pub fn fixated(&self) -> Option<&'a str> {
+        let first = self.individuals[0];
+        self.individuals
+            .iter()
+            .all(|&strategy| strategy == first)
+            .then(|| self.names[first])
+    }
+
+    /// Take one step of the Moran process. Each individual's fitness is its average payoff
+    /// against every other individual. One individual is chosen to reproduce with probability
+    /// proportional to its fitness, and one is chosen uniformly at random to die and be replaced
+    /// by a copy of the reproducer. If every fitness is zero the reproducer is also chosen
+    /// uniformly.
+    pub fn moran_step(&mut self) {
+        let others = (self.individuals.len() - 1) as f64;
+        let mut strategy_counts = vec![0usize; self.names.len()];
+        for &strategy in &self.individuals {
+            strategy_counts[strategy] += 1;
+        }
+
+        let fitness = self
+            .individuals
+            .iter()
+            .map(|&me| {
+                let total = strategy_counts
+                    .iter()
+                    .enumerate()
+                    .map(|(them, &count)| {
+                        let opponents = if them == me { count - 1 } else { count };
+                        self.payoffs[me][them] * opponents as f64
+                    })
+                    .sum::<f64>();
+                total / others
+            })
+            .collect::<Vec<_>>();
+
+        let total = fitness.iter().sum::<f64>();
+        let reproducer = if total > 0.0 {
+            let mut target = self.rng.next_f64() * total;
+            fitness
+                .iter()
+                .position(|&f| {
+                    target -= f;
+                    target < 0.0
+                })
+                .unwrap_or(fitness.len() - 1)
+        } else {
+            self.rng.below(self.individuals.len())
+        };
+        let dies = self.rng.below(self.individuals.len());
+
+        self.individuals[dies] = self.individuals[reproducer];
+    }
+
+    /// Take Moran steps until only one strategy is left, and return it.
+    pub fn run_to_fixation(&mut self) -> &'a str {
+        loop {
+            if let Some(winner) = self.fixated() {
+                return winner;
+            }
+            self.moran_step();
+        }
+    }
+}
+
+/// Estimate how likely each strategy is to take over the population described by `counts`, by
+/// running the Moran process to fixation `trials` times. Trial `i` is seeded with `seed + i`, so
+/// the estimate can be reproduced. Strategies that never took over have a probability of 0, as
+/// does every strategy when `trials` is 0.
+///
+/// See [`Population::new`] for the requirements on the names and the population size.
+///
+/// Always Defect exploits Always Cooperate, so it usually takes over an evenly split
+/// population, and even a single defector takes over far more often than the one time in ten
+/// that chance alone would give it:
+///
+/// ```
+/// # use prisoners_dilemma_competition::*;
+/// let entrants = [Entrant::new::<AlwaysDefect>(), Entrant::new::<AlwaysCooperate>()];
+/// let even = [("Always Defect", 5), ("Always Cooperate", 5)];
+/// assert!(fixation_probabilities(&entrants, &even, 100, 0)["Always Defect"] > 0.9);
+///
+/// let invader = [("Always Defect", 1), ("Always Cooperate", 9)];
+/// let probabilities = fixation_probabilities(&entrants, &invader, 100, 0);
+/// assert!(probabilities["Always Defect"] > 0.3);
+/// assert!((probabilities.values().sum::<f64>() - 1.0).abs() < 1e-9);
+/// ```
+pub fn fixation_probabilities<'a>(
+    entrants: &[Entrant],
+    counts: &[(&'a str, usize)],
+    trials: usize,
+    seed: u64,
+) -> HashMap<&'a str, f64> {
+    let population = Population::new(entrants, counts, seed);
+    let mut wins = counts
+        .iter()
+        .map(|&(name, _)| (name, 0usize))
+        .collect::<HashMap<_, _>>();
+
+    for trial in 0..trials {
+        let mut population = population.clone();
+        population.rng = Rng::new(seed.wrapping_add(trial as u64));
+        *wins.get_mut(population.run_to_fixation()).unwrap() += 1;
+    }
+
+    wins.into_iter()
+        .map(|(name, won)| (name, won as f64 / trials.max(1) as f64))
+        .collect()
+}
@@ -0,0 +1,198 @@
+//! Batches of games described in JSON, so that an experiment can be checked into a repository
+//! and rerun exactly.
+//!
+//! A spec is a JSON array with one object per game:
+//!
+//! ```json
+//! [
+//!     { "p1": "Tit for Tat", "p2": "Always Defect" },
+//!     {
+//!         "p1": "Pavlov",
+//!         "p2": "Grim Trigger",
+//!         "rounds": 50,
+//!         "noise": 0.05,
+//!         "seed": 7,
+//!         "payoffs": { "reward": 3, "temptation": 5, "punishment": 1, "sucker": 0 }
+//!     }
+//! ]
+//! ```
+//!
+//! Only the strategies are required. The rest default to `NUM_TURNS` rounds, no noise, a seed
+//! of 0, and the default payoffs.
+
+use std::fmt;
+
+use crate::{
+    integer_from_json, string_from_json, DynGame, DynStrategy, FromJson, GameResult, Json,
+    JsonError, PayoffMatrix, Player, StrategyFailure, ToJson, NUM_TURNS,
+};
+
+/// One game in an experiment. The strategies are looked up by name when the experiment runs,
+/// with the same forgiving matching as the command line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matchup {
+    pub p1: String,
+    pub p2: String,
+    pub rounds: usize,
+    /// The chance that each move is flipped by mistake
+    pub noise: f64,
+    /// The seed for the noise
+    pub seed: u64,
+    pub payoffs: PayoffMatrix,
+}
+
+/// Why an experiment could not be run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExperimentError {
+    /// The position of the offending matchup in the spec, counting from 0, or `None` if the
+    /// spec as a whole could not be read
+    pub matchup: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for ExperimentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.matchup {
+            Some(matchup) => write!(f, "matchup {matchup}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for ExperimentError {}
+
+/// Run every matchup in the JSON `spec` and return the results as a JSON array of game
+/// summaries, in the same order. Every matchup is checked before any game is played, so an
+/// unknown strategy or invalid payoffs in any entry fail the whole batch.
+pub fn run_experiment(spec: &str) -> Result<Json, ExperimentError> {
+    let whole_spec = |error: JsonError| ExperimentError {
+        matchup: None,
+        message: error.to_string(),
+    };
+    let spec = Json::parse(spec).map_err(whole_spec)?;
+    let entries = spec
+        .as_array()
+        .ok_or_else(|| whole_spec(JsonError("expected a list of matchups".into())))?;
+    let matchups = entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            Matchup::from_json(entry).map_err(|error| ExperimentError {
+                matchup: Some(i),
+                message: error.to_string(),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let results = run_matchups(&matchups)?;
+    Ok(Json::Array(results.iter().map(ToJson::to_json).collect()))
+}
+
+/// Play every matchup and summarize each game, in order. Every matchup is checked before any
+/// game is played, so one bad entry fails the whole batch.
+pub fn run_matchups(matchups: &[Matchup]) -> Result<Vec<GameResult>, ExperimentError> {
+    let games = matchups
+        .iter()
+        .enumerate()
+        .map(|(i, matchup)| {
+            matchup.new_game().map_err(|message| ExperimentError {
+                matchup: Some(i),
+                message,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(games
+        .into_iter()
+        .zip(matchups)
+        .map(|(mut game, matchup)| {
+            game.play_n_rounds(matchup.rounds);
+            game.result()
+        })
+        .collect())
+}
+
+impl Matchup {
+    /// Set up the game, or describe what is wrong with the matchup.
+    fn new_game(&self) -> Result<DynGame, String> {
+        let p1 = self
+            .p1
+            .parse::<Box<dyn DynStrategy>>()
+            .map_err(|e| e.to_string())?;
+        let p2 = self
+            .p2
+            .parse::<Box<dyn DynStrategy>>()
+            .map_err(|e| e.to_string())?;
+        let PayoffMatrix {
+            reward,
+            temptation,
+            punishment,
+            sucker,
+        } = self.payoffs;
+        let payoffs =
+            PayoffMatrix::new(reward, temptation, punishment, sucker).map_err(|e| e.to_string())?;
+        if !(0.0..=1.0).contains(&self.noise) {
+            return Err(format!(
+                "noise must be between 0 and 1, found {}",
+                self.noise
+            ));
+        }
+
+        let mut game = DynGame::with_payoffs(p1, p2, payoffs);
+        game.set_noise(self.noise, self.seed);
+        Ok(game)
+    }
+}
+
+impl FromJson for Matchup {
+    fn from_json(json: &Json) -> Result<Self, JsonError> {
+        Ok(Self {
+            p1: string_from_json(json.field("p1")?)?,
+            p2: string_from_json(json.field("p2")?)?,
+            rounds: json
+                .get("rounds")
+                .map_or(Ok(NUM_TURNS), integer_from_json)?,
+            noise: json.get("noise").map_or(Ok(0.0), |noise| {
+                noise
+                    .as_f64()
+                    .ok_or_else(|| JsonError(format!("expected a number, found {noise}")))
+            })?,
+            seed: json.get("seed").map_or(Ok(0), integer_from_json)?,
+            payoffs: json
+                .get("payoffs")
+                .map_or(Ok(PayoffMatrix::default()), PayoffMatrix::from_json)?,
+        })
+    }
+}
+
+impl ToJson for GameResult {
+    fn to_json(&self) -> Json {
+        Json::Object(vec![
+            ("p1_name".into(), Json::String(self.p1_name.clone())),
+            ("p2_name".into(), Json::String(self.p2_name.clone())),
+            ("p1_score".into(), Json::Integer(self.p1_score as i128)),
+            ("p2_score".into(), Json::Integer(self.p2_score as i128)),
+            ("rounds".into(), Json::Integer(self.rounds as i128)),
+            ("p1_coop_rate".into(), Json::Float(self.p1_coop_rate)),
+            ("p2_coop_rate".into(), Json::Float(self.p2_coop_rate)),
+            (
+                "failures".into(),
+                Json::Array(self.failures.iter().map(ToJson::to_json).collect()),
+            ),
+        ])
+    }
+}
+
+impl ToJson for StrategyFailure {
+    fn to_json(&self) -> Json {
+        let player = match self.player {
+            Player::One => 1,
+            Player::Two => 2,
+        };
+        Json::Object(vec![
+            ("player".into(), Json::Integer(player)),
+            ("round".into(), Json::Integer(self.round as i128)),
+            ("error".into(), Json::String(self.error.to_string())),
+        ])
+    }
+}
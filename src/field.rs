@@ -0,0 +1,138 @@
+//! Strategies that remember what they have learned about the whole field of opponents across
+//! the games of a tournament.
+
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::{CooperateOrDefect, DynStrategy, Entrant, HistoryView};
+
+/// A strategy with two tiers of memory. Like [`Strategy::State`](crate::Strategy::State), a
+/// fresh `GameState` is created at the start of every game, so anything kept in it, such as
+/// [`Gradual`](crate::Gradual)'s count of the opponent's defections, starts over against each
+/// new opponent. A single `TournamentState` is shared by every game the strategy plays as one
+/// [`Entrant`], so it can build up a picture of the field as a whole.
+///
+/// Here is a strategy that cooperates on the first move only while the opponents it has met
+/// have cooperated at least as often as they defected:
+///
+/// ```
+/// # use prisoners_dilemma_competition::{CooperateOrDefect::{self, *}, FieldStrategy, HistoryView};
+/// struct ReadTheRoom;
+///
+/// #[derive(Default)]
+/// struct FieldCounts {
+///     cooperations: usize,
+///     defections: usize,
+/// }
+///
+/// impl FieldStrategy for ReadTheRoom {
+///     const NAME: &'static str = "Read the Room";
+///     type GameState = ();
+///     type TournamentState = FieldCounts;
+///
+///     fn next_move(
+///         history: &HistoryView<'_>,
+///         _game: &mut (),
+///         field: &mut FieldCounts,
+///     ) -> CooperateOrDefect {
+///         match history.their_last_move() {
+///             Some(Cooperate) => field.cooperations += 1,
+///             Some(Defect) => field.defections += 1,
+///             None if field.defections > field.cooperations => return Defect,
+///             None => return Cooperate,
+///         }
+///         history.their_last_move().unwrap()
+///     }
+/// }
+/// ```
+pub trait FieldStrategy {
+    const NAME: &'static str;
+
+    /// A one line summary of how the strategy plays, for listings and reports.
+    const DESCRIPTION: &'static str = "";
+
+    /// Memory that is reset at the beginning of each game.
+    type GameState: Default;
+
+    /// Memory that is kept from one game to the next for as long as the entrant exists.
+    type TournamentState: Default + Send;
+
+    /// Calculate your next move, with access to both tiers of memory.
+    fn next_move(
+        history: &HistoryView<'_>,
+        game: &mut Self::GameState,
+        tournament: &mut Self::TournamentState,
+    ) -> CooperateOrDefect;
+}
+
+/// A player following the `FieldStrategy` `S` in one game, see [`Entrant::field_aware`].
+pub struct FieldInstance<S: FieldStrategy> {
+    game: S::GameState,
+    tournament: Arc<Mutex<S::TournamentState>>,
+}
+
+impl<S: FieldStrategy> FieldInstance<S> {
+    /// A player with a fresh game state that shares `tournament` with every other player
+    /// created from the same handle.
+    pub fn new(tournament: Arc<Mutex<S::TournamentState>>) -> Self {
+        Self {
+            game: S::GameState::default(),
+            tournament,
+        }
+    }
+}
+
+impl<S: FieldStrategy> DynStrategy for FieldInstance<S> {
+    fn name(&self) -> &str {
+        S::NAME
+    }
+
+    fn description(&self) -> &str {
+        S::DESCRIPTION
+    }
+
+    /// The player's moves depend on the games it played before, not just on the history of
+    /// this one, so its games cannot be reused.
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+
+    fn next_move(
+        &mut self,
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+    ) -> CooperateOrDefect {
+        self.next_move_with_history(&HistoryView::new(my_moves, their_moves))
+    }
+
+    fn next_move_with_history(&mut self, history: &HistoryView<'_>) -> CooperateOrDefect {
+        // A panic in another game leaves the state as it was, which is as good as any
+        let mut tournament = self
+            .tournament
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        S::next_move(history, &mut self.game, &mut tournament)
+    }
+}
+
+impl Entrant {
+    /// An entrant for a [`FieldStrategy`]. Every player it creates gets a fresh game state and
+    /// shares the entrant's one tournament state, which lives as long as the entrant does, so
+    /// reusing an entrant for several tournaments carries what it learned from one to the next.
+    ///
+    /// Games are played in order by [`run_tournament_with_config`](crate::run_tournament_with_config),
+    /// so the tournament state is always built up the same way. The parallel tournaments play
+    /// games in whatever order the threads reach them, so their results can vary from run to
+    /// run when there are field aware entrants.
+    pub fn field_aware<S>() -> Self
+    where
+        S: FieldStrategy + 'static,
+        S::GameState: 'static,
+        S::TournamentState: 'static,
+    {
+        let tournament = Arc::new(Mutex::new(S::TournamentState::default()));
+        Self::seeded(S::NAME, move |_| {
+            Box::new(FieldInstance::<S>::new(Arc::clone(&tournament)))
+        })
+        .sharing_state()
+    }
+}
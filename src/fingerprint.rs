@@ -0,0 +1,107 @@
+//! Identifying strategies by how they respond to a fixed set of situations, to catch the same
+//! strategy being entered twice under different names.
+
+use std::collections::HashMap;
+
+use crate::{
+    CooperateOrDefect, CooperateOrDefect::*, DynStrategy, Entrant, Strategy, StrategyInstance,
+};
+
+/// Fingerprints cover every pair of histories up to this many rounds long
+const FINGERPRINT_ROUNDS: usize = 3;
+
+/// The moves that the strategy `S` makes after each of a fixed battery of histories: every
+/// possible pair of histories of up to three rounds, shortest first. Strategies with the same
+/// fingerprint respond identically to every short history, and so are very likely the same
+/// strategy.
+pub fn fingerprint<S>() -> Vec<CooperateOrDefect>
+where
+    S: Strategy + 'static,
+    S::State: 'static,
+{
+    fingerprint_with(StrategyInstance::<S>::boxed)
+}
+
+/// Group together the entrants whose fingerprints are identical, see [`fingerprint`]. Only groups
+/// of two or more are returned, in the order their first members were entered. Entrants that
+/// make random choices are fingerprinted using the seed 0.
+pub fn duplicate_strategies(entrants: &[Entrant]) -> Vec<Vec<&'static str>> {
+    let mut groups: Vec<Vec<&'static str>> = Vec::new();
+    let mut group_of: HashMap<Vec<CooperateOrDefect>, usize> = HashMap::new();
+    for entrant in entrants {
+        let fingerprint = fingerprint_with(|| entrant.new_player(0));
+        match group_of.get(&fingerprint) {
+            Some(&group) => groups[group].push(entrant.name()),
+            None => {
+                group_of.insert(fingerprint, groups.len());
+                groups.push(vec![entrant.name()]);
+            }
+        }
+    }
+
+    groups.retain(|group| group.len() > 1);
+    groups
+}
+
+/// The complete behavior of the strategy `S` over short games: its response to every possible
+/// pair of histories of up to `k` rounds, keyed by its own history and then its opponent's. The
+/// empty histories give its opening move. There are `4^k` pairs of histories of length `k`, so
+/// this is only practical for small `k`, but for a deterministic strategy it is an exhaustive
+/// specification of how it plays games that short.
+pub fn brute_force_profile<S>(
+    k: usize,
+) -> HashMap<(Vec<CooperateOrDefect>, Vec<CooperateOrDefect>), CooperateOrDefect>
+where
+    S: Strategy + 'static,
+    S::State: 'static,
+{
+    (0..=k)
+        .flat_map(histories)
+        .map(|(my_moves, their_moves)| {
+            let response = respond(StrategyInstance::<S>::boxed(), &my_moves, &their_moves);
+            ((my_moves, their_moves), response)
+        })
+        .collect()
+}
+
+/// Fingerprint the players created by `new_player`, using a fresh one for each history.
+fn fingerprint_with<F>(new_player: F) -> Vec<CooperateOrDefect>
+where
+    F: Fn() -> Box<dyn DynStrategy>,
+{
+    (0..=FINGERPRINT_ROUNDS)
+        .flat_map(histories)
+        .map(|(my_moves, their_moves)| respond(new_player(), &my_moves, &their_moves))
+        .collect()
+}
+
+/// The move that a fresh `player` makes after the given histories.
+fn respond(
+    mut player: Box<dyn DynStrategy>,
+    my_moves: &[CooperateOrDefect],
+    their_moves: &[CooperateOrDefect],
+) -> CooperateOrDefect {
+    // Show the player the history one round at a time, as in a real game, so that strategies
+    // with their own state build it up properly
+    (0..=my_moves.len())
+        .map(|round| player.next_move(&my_moves[..round], &their_moves[..round]))
+        .last()
+        .expect("there is always at least one round to respond to")
+}
+
+/// Every possible pair of histories that are `rounds` rounds long, in a fixed order.
+fn histories(rounds: usize) -> Vec<(Vec<CooperateOrDefect>, Vec<CooperateOrDefect>)> {
+    (0..1usize << (2 * rounds))
+        .map(|bits| {
+            let moves = |offset: usize| {
+                (0..rounds)
+                    .map(|round| match bits >> (2 * round + offset) & 1 {
+                        0 => Cooperate,
+                        _ => Defect,
+                    })
+                    .collect()
+            };
+            (moves(0), moves(1))
+        })
+        .collect()
+}
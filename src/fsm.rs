@@ -0,0 +1,314 @@
+//! Strategies described as finite state machines, so that new strategies can be defined as data
+//! instead of by implementing [`Strategy`](crate::Strategy).
+//!
+//! Machines can also be written in `.strat` files, so that strategies can be defined without
+//! writing any Rust. Tit for Tat looks like this:
+//!
+//! ```text
+//! # Copy whatever the opponent did last
+//! name: Tit for Tat
+//! opening: C
+//! initial: copy
+//!
+//! copy CC -> copy C
+//! copy CD -> copy D
+//! copy DC -> copy C
+//! copy DD -> copy D
+//! ```
+//!
+//! Each transition line names a state, the outcome of the last round as this player's move
+//! followed by the opponent's, the state to move to, and the move to play next. States can have
+//! any name without spaces. The `initial` line can be left out, in which case the machine starts
+//! in the state named on the first transition line. Everything after a `#` is a comment.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt, fs,
+    path::Path,
+};
+
+use crate::{outcome, CooperateOrDefect, CooperateOrDefect::*, DynStrategy, Outcome};
+
+/// Identifies one of the states of an [`FsmStrategy`].
+pub type StateId = usize;
+
+/// A strategy that is a Mealy machine on the last outcome. It plays `opening` on the first move
+/// and starts in `initial_state`. After each round it looks up its current state and that
+/// round's outcome in the transition table, which gives the state to move to and the move to
+/// play next. Outcomes are seen from the machine's point of view, as if it were player 1, so
+/// `P1Exploited` means that it cooperated and its opponent defected.
+///
+/// Tit for Tat, for example, needs only one state:
+///
+/// ```
+/// # use std::collections::HashMap;
+/// # use prisoners_dilemma_competition::{CooperateOrDefect::*, Outcome::*, *};
+/// let tit_for_tat = FsmStrategy::new(
+///     "Tit for Tat",
+///     HashMap::from([
+///         ((0, MutualCooperation), (0, Cooperate)),
+///         ((0, P1Exploited), (0, Defect)),
+///         ((0, P2Exploited), (0, Cooperate)),
+///         ((0, MutualDefection), (0, Defect)),
+///     ]),
+///     0,
+///     Cooperate,
+/// );
+///
+/// // It plays the same moves as the built-in Tit for Tat
+/// for opponent in ["Always Defect", "Pavlov", "Gradual", "Random", "Joss"] {
+///     let mut machine = DynGame::new(Box::new(tit_for_tat.clone()), create_strategy(opponent)?);
+///     machine.play_n_rounds(100);
+///     let mut built_in = DynGame::new(
+///         StrategyInstance::<TitForTat>::boxed(),
+///         create_strategy(opponent)?,
+///     );
+///     built_in.play_n_rounds(100);
+///     assert_eq!(machine.player_1_moves(), built_in.player_1_moves(), "{opponent}");
+///     assert_eq!(machine.player_2_moves(), built_in.player_2_moves(), "{opponent}");
+/// }
+/// # Ok::<(), UnknownStrategy>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct FsmStrategy {
+    name: String,
+    transitions: HashMap<(StateId, Outcome), (StateId, CooperateOrDefect)>,
+    opening: CooperateOrDefect,
+    state: StateId,
+}
+
+impl FsmStrategy {
+    /// # Panics
+    ///
+    /// Panics if some state that the machine can reach is missing a transition for one of the
+    /// four outcomes.
+    pub fn new(
+        name: impl Into<String>,
+        transitions: HashMap<(StateId, Outcome), (StateId, CooperateOrDefect)>,
+        initial_state: StateId,
+        opening: CooperateOrDefect,
+    ) -> Self {
+        let name = name.into();
+        if let Some((state, outcome)) = missing_transition(&transitions, initial_state) {
+            panic!("{name} has no transition from state {state} after {outcome:?}");
+        }
+
+        Self {
+            name,
+            transitions,
+            opening,
+            state: initial_state,
+        }
+    }
+}
+
+/// Why a `.strat` file could not be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StratError {
+    /// The line the problem is on, counting from 1, or `None` if it is with the file as a whole
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for StratError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for StratError {}
+
+impl FsmStrategy {
+    /// Read a machine from a `.strat` file, see the [module documentation](self).
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, StratError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|e| StratError {
+            line: None,
+            message: format!("could not read {}: {e}", path.display()),
+        })?;
+        Self::from_strat(&text)
+    }
+
+    /// Read a machine from the text of a `.strat` file, see the [module documentation](self).
+    pub fn from_strat(text: &str) -> Result<Self, StratError> {
+        let mut name = None;
+        let mut opening = None;
+        let mut initial = None;
+        let mut states: Vec<&str> = Vec::new();
+        let mut transitions = HashMap::new();
+
+        for (number, line) in text.lines().enumerate() {
+            let error = |message: String| StratError {
+                line: Some(number + 1),
+                message,
+            };
+
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some((key, value)) = line.split_once(':') {
+                let value = value.trim();
+                let setting = match key.trim() {
+                    "name" => &mut name,
+                    "opening" => &mut opening,
+                    "initial" => &mut initial,
+                    key => return Err(error(format!("unknown setting `{key}`"))),
+                };
+                if setting.replace(value).is_some() {
+                    return Err(error(format!("`{}` is given twice", key.trim())));
+                }
+                continue;
+            }
+
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let &[from, last_round, "->", to, next_move] = words.as_slice() else {
+                return Err(error(format!(
+                    "expected a transition such as `state CD -> state D`, found `{line}`"
+                )));
+            };
+            let key = (
+                state_id(&mut states, from),
+                parse_outcome(last_round).map_err(error)?,
+            );
+            let value = (
+                state_id(&mut states, to),
+                parse_move(next_move).map_err(error)?,
+            );
+            if transitions.insert(key, value).is_some() {
+                return Err(error(format!(
+                    "state `{from}` already has a transition after {last_round}"
+                )));
+            }
+        }
+
+        let missing = |setting: &str| StratError {
+            line: None,
+            message: format!("missing the `{setting}` line"),
+        };
+        let name = name.ok_or_else(|| missing("name"))?;
+        let opening =
+            parse_move(opening.ok_or_else(|| missing("opening"))?).map_err(|message| {
+                StratError {
+                    line: None,
+                    message: format!("invalid opening: {message}"),
+                }
+            })?;
+        let initial_state = match initial {
+            Some(initial) => {
+                states
+                    .iter()
+                    .position(|&s| s == initial)
+                    .ok_or_else(|| StratError {
+                        line: None,
+                        message: format!("the initial state `{initial}` is not in any transition"),
+                    })?
+            }
+            None if states.is_empty() => {
+                return Err(StratError {
+                    line: None,
+                    message: "there are no transitions".into(),
+                })
+            }
+            None => 0,
+        };
+        if let Some((state, outcome)) = missing_transition(&transitions, initial_state) {
+            let (mine, theirs) = outcome_moves(outcome);
+            return Err(StratError {
+                line: None,
+                message: format!(
+                    "state `{}` has no transition after {mine}{theirs}",
+                    states.get(state).copied().unwrap_or("?")
+                ),
+            });
+        }
+
+        Ok(Self::new(name, transitions, initial_state, opening))
+    }
+}
+
+/// The id of the state called `state`, giving it the next unused id if it has none yet.
+fn state_id<'a>(states: &mut Vec<&'a str>, state: &'a str) -> StateId {
+    match states.iter().position(|&s| s == state) {
+        Some(id) => id,
+        None => {
+            states.push(state);
+            states.len() - 1
+        }
+    }
+}
+
+/// A state that can be reached from `initial_state` but has no transition for some outcome, if
+/// there is one.
+fn missing_transition(
+    transitions: &HashMap<(StateId, Outcome), (StateId, CooperateOrDefect)>,
+    initial_state: StateId,
+) -> Option<(StateId, Outcome)> {
+    let mut reachable = HashSet::from([initial_state]);
+    let mut unvisited = vec![initial_state];
+    while let Some(state) = unvisited.pop() {
+        for outcome in Outcome::ALL {
+            let Some(&(next, _)) = transitions.get(&(state, outcome)) else {
+                return Some((state, outcome));
+            };
+            if reachable.insert(next) {
+                unvisited.push(next);
+            }
+        }
+    }
+    None
+}
+
+fn parse_move(text: &str) -> Result<CooperateOrDefect, String> {
+    match text {
+        "C" => Ok(Cooperate),
+        "D" => Ok(Defect),
+        _ => Err(format!("expected a move, `C` or `D`, found `{text}`")),
+    }
+}
+
+/// Read an outcome written as this player's move followed by the opponent's, such as `CD`.
+fn parse_outcome(text: &str) -> Result<Outcome, String> {
+    let error = || format!("expected an outcome such as `CD`, found `{text}`");
+    let mut moves = text.chars().map(|c| parse_move(&c.to_string()));
+    match (moves.next(), moves.next(), moves.next()) {
+        (Some(Ok(mine)), Some(Ok(theirs)), None) => Ok(outcome(mine, theirs)),
+        _ => Err(error()),
+    }
+}
+
+/// The moves of the player whose point of view `outcome` is named from, and of its opponent.
+fn outcome_moves(outcome: Outcome) -> (CooperateOrDefect, CooperateOrDefect) {
+    match outcome {
+        Outcome::MutualCooperation => (Cooperate, Cooperate),
+        Outcome::P1Exploited => (Cooperate, Defect),
+        Outcome::P2Exploited => (Defect, Cooperate),
+        Outcome::MutualDefection => (Defect, Defect),
+    }
+}
+
+impl DynStrategy for FsmStrategy {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn next_move(
+        &mut self,
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+    ) -> CooperateOrDefect {
+        let (Some(my_last), Some(their_last)) = (my_moves.last(), their_moves.last()) else {
+            return self.opening;
+        };
+
+        // Every reachable state was checked for a full set of transitions in `new`
+        let (next_state, next_move) =
+            &self.transitions[&(self.state, outcome(*my_last, *their_last))];
+        self.state = *next_state;
+        *next_move
+    }
+}
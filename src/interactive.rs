@@ -0,0 +1,125 @@
+//! Playing a game by hand against one of the strategies, one round at a time, to get a feel for
+//! how it behaves.
+
+use std::{
+    cell::Cell,
+    io::{self, BufRead, Write},
+    rc::Rc,
+};
+
+use crate::{
+    CooperateOrDefect, CooperateOrDefect::*, DynGame, DynStrategy, GameConfig, MatchSummary,
+};
+
+/// The human's side of the game, which plays whatever move was last typed in.
+struct Human(Rc<Cell<CooperateOrDefect>>);
+
+impl DynStrategy for Human {
+    fn name(&self) -> &str {
+        "You"
+    }
+
+    fn next_move(
+        &mut self,
+        _my_moves: &[CooperateOrDefect],
+        _their_moves: &[CooperateOrDefect],
+    ) -> CooperateOrDefect {
+        self.0.get()
+    }
+}
+
+/// Play a game against `opponent` with the rounds and payoffs in `config`, reading the human's
+/// moves from `input` and writing prompts and results to `output`. Each round asks for `C` or
+/// `D`, in either case, and asks again after anything else. After each round it shows both
+/// moves and the running score, and at the end the summary of the game.
+///
+/// If `input` runs out before the last round, the game ends early and the summary covers the
+/// rounds that were played. Noise is not used, so every move is played as it was chosen, and
+/// the command line refuses `--noise` and `--seed` with `play`.
+///
+/// ```
+/// # use prisoners_dilemma_competition::{create_strategy, play_interactive, GameConfig};
+/// let config = GameConfig {
+///     rounds: 3,
+///     ..GameConfig::default()
+/// };
+/// let mut output = Vec::new();
+/// let summary = play_interactive(
+///     create_strategy("Tit for Tat")?,
+///     &config,
+///     "c\nmaybe\nD\nc\n".as_bytes(),
+///     &mut output,
+/// )?;
+/// assert_eq!(summary.rounds, 3);
+/// assert_eq!(summary.scores, (10 + 20 - 5, 10 - 5 + 20));
+/// assert!(String::from_utf8(output)?.contains("`maybe` is not a move"));
+///
+/// // Twenty scripted rounds: ten cooperations, then ten defections that Tit for Tat answers
+/// // from the second one on
+/// let config = GameConfig {
+///     rounds: 20,
+///     ..GameConfig::default()
+/// };
+/// let input = "c\n".repeat(10) + &"d\n".repeat(10);
+/// let opponent = create_strategy("Tit for Tat")?;
+/// let summary = play_interactive(opponent, &config, input.as_bytes(), Vec::new())?;
+/// assert_eq!(summary.rounds, 20);
+/// assert_eq!(summary.scores, (10 * 10 + 20 + 9 * 2, 10 * 10 - 5 + 9 * 2));
+///
+/// // The input runs out after the first round
+/// let opponent = create_strategy("Pavlov")?;
+/// let summary = play_interactive(opponent, &config, "d\n".as_bytes(), Vec::new())?;
+/// assert_eq!(summary.rounds, 1);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn play_interactive<R: BufRead, W: Write>(
+    opponent: Box<dyn DynStrategy>,
+    config: &GameConfig,
+    mut input: R,
+    mut output: W,
+) -> io::Result<MatchSummary> {
+    let human_move = Rc::new(Cell::new(Cooperate));
+    let mut game = DynGame::with_payoffs(
+        Box::new(Human(Rc::clone(&human_move))),
+        opponent,
+        config.payoffs,
+    );
+    let opponent_name = game.names().1.to_string();
+
+    'rounds: for round in 1..=config.rounds {
+        let chosen = loop {
+            write!(
+                output,
+                "Round {round} of {}. Your move (C/D): ",
+                config.rounds
+            )?;
+            output.flush()?;
+            let mut line = String::new();
+            if input.read_line(&mut line)? == 0 {
+                writeln!(output)?;
+                break 'rounds;
+            }
+            match line.trim() {
+                "C" | "c" => break Cooperate,
+                "D" | "d" => break Defect,
+                other => writeln!(
+                    output,
+                    "`{other}` is not a move, enter C to cooperate or D to defect"
+                )?,
+            }
+        };
+        human_move.set(chosen);
+
+        let (mine, theirs) = game.play_next_round();
+        let (my_score, their_score) = game.calculate_score();
+        writeln!(
+            output,
+            "You played {mine}, {opponent_name} played {theirs}. \
+             Score: you {my_score}, {opponent_name} {their_score}"
+        )?;
+    }
+
+    let summary = game.summary();
+    write!(output, "\n{summary}")?;
+    Ok(summary)
+}
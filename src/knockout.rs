@@ -0,0 +1,196 @@
+//! Single-elimination tournaments, in which strategies play head-to-head matches and only the
+//! winners go on to the next round.
+
+use crate::{tournament::play_seeded_game, Entrant, TournamentConfig, Winner};
+
+/// One head-to-head match in a knockout tournament.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KnockoutMatch {
+    /// The better seeded of the two entrants
+    pub top: &'static str,
+    /// The other entrant, or `None` if `top` has a bye into the next round
+    pub bottom: Option<&'static str>,
+    /// The number of games won by `top` and by `bottom`. Drawn games count for neither.
+    pub wins: (usize, usize),
+    /// The total scores of `top` and `bottom` over all the games of the match
+    pub scores: (isize, isize),
+    pub winner: &'static str,
+}
+
+/// The full record of a knockout tournament, see [`run_knockout`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bracket {
+    /// The matches of each round, from the first round to the final. Within a round, the
+    /// winners of neighbouring matches meet in the next round.
+    pub rounds: Vec<Vec<KnockoutMatch>>,
+    /// The winner of the final, or `None` if there were no entrants
+    pub champion: Option<&'static str>,
+}
+
+/// Play a single-elimination tournament. The entrants are seeded in the order they are given,
+/// best first, and placed in a standard bracket so that the top two seeds can only meet in the
+/// final. When the number of entrants is not a power of two, the best seeds get byes through the
+/// first round.
+///
+/// Each match is the best of `best_of` games, with the two entrants swapping positions after
+/// every game. The games are played with the rounds and noise in `config`, and each gets its
+/// own seeds derived from `config.seed`. The entrant that wins more games goes through. If they
+/// won equally many, for example because every game was drawn, the one with the higher total
+/// score goes through, and if that is also level the better seed does.
+///
+/// With five entrants the bracket has room for eight, so the top three seeds get byes and only
+/// the fourth and fifth seeds play in the first round:
+///
+/// ```
+/// # use prisoners_dilemma_competition::*;
+/// let entrants = [
+///     Entrant::new::<TitForTat>(),
+///     Entrant::new::<AlwaysDefect>(),
+///     Entrant::new::<Pavlov>(),
+///     Entrant::new::<GrimTrigger>(),
+///     Entrant::new::<AlwaysCooperate>(),
+/// ];
+/// let config = TournamentConfig {
+///     rounds: 20,
+///     ..TournamentConfig::default()
+/// };
+/// let bracket = run_knockout(&entrants, 3, &config);
+///
+/// let first_round = &bracket.rounds[0];
+/// assert_eq!(first_round.iter().filter(|m| m.bottom.is_none()).count(), 3);
+/// // Every game between these two is drawn, so the better seed goes through
+/// let played = first_round.iter().find(|m| m.bottom.is_some()).unwrap();
+/// assert_eq!((played.top, played.bottom), ("Grim Trigger", Some("Always Cooperate")));
+/// assert_eq!((played.wins, played.scores.0 - played.scores.1), ((0, 0), 0));
+/// assert_eq!(played.winner, "Grim Trigger");
+///
+/// assert_eq!(bracket.rounds.len(), 3);
+/// assert_eq!(bracket.champion, Some("Always Defect"));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `best_of` is even, since a match of an even number of games can be tied:
+///
+/// ```should_panic
+/// # use prisoners_dilemma_competition::*;
+/// let entrants = [Entrant::new::<TitForTat>(), Entrant::new::<AlwaysDefect>()];
+/// run_knockout(&entrants, 2, &TournamentConfig::default());
+/// ```
+pub fn run_knockout(entrants: &[Entrant], best_of: usize, config: &TournamentConfig) -> Bracket {
+    assert!(
+        best_of % 2 == 1,
+        "matches must be the best of an odd number of games, found {best_of}"
+    );
+
+    // Positions in `entrants`, which are also the seeds counting from 0
+    let mut remaining: Vec<Option<usize>> = bracket_order(entrants.len())
+        .into_iter()
+        .map(|seed| (seed < entrants.len()).then_some(seed))
+        .collect();
+    let mut rounds = Vec::new();
+    while remaining.len() > 1 {
+        let (matches, winners): (Vec<_>, Vec<_>) = remaining
+            .chunks(2)
+            .enumerate()
+            .map(|(number, pair)| {
+                let (top, bottom) = match (pair[0], pair[1]) {
+                    (Some(a), Some(b)) => (a.min(b), Some(a.max(b))),
+                    (Some(a), None) | (None, Some(a)) => (a, None),
+                    (None, None) => unreachable!("a bracket never pairs two byes"),
+                };
+                let config = TournamentConfig {
+                    seed: config.seed ^ ((rounds.len() as u64) << 48 | (number as u64) << 32),
+                    ..*config
+                };
+                play_match(entrants, top, bottom, best_of, &config)
+            })
+            .unzip();
+        remaining = winners.into_iter().map(Some).collect();
+        rounds.push(matches);
+    }
+
+    let champion = remaining
+        .first()
+        .copied()
+        .flatten()
+        .map(|seed| entrants[seed].name());
+    Bracket { rounds, champion }
+}
+
+/// Play the match between the entrants at positions `top` and `bottom`, returning it along with
+/// the position of the winner.
+fn play_match(
+    entrants: &[Entrant],
+    top: usize,
+    bottom: Option<usize>,
+    best_of: usize,
+    config: &TournamentConfig,
+) -> (KnockoutMatch, usize) {
+    let Some(bottom) = bottom else {
+        let name = entrants[top].name();
+        let bye = KnockoutMatch {
+            top: name,
+            bottom: None,
+            wins: (0, 0),
+            scores: (0, 0),
+            winner: name,
+        };
+        return (bye, top);
+    };
+
+    let mut wins = (0, 0);
+    let mut scores: (isize, isize) = (0, 0);
+    for game in 0..best_of {
+        let config = TournamentConfig {
+            seed: config.seed.wrapping_add(game as u64),
+            ..*config
+        };
+        let (top_score, bottom_score) = if game % 2 == 0 {
+            play_seeded_game(entrants, top, bottom, &config).calculate_score()
+        } else {
+            let (bottom_score, top_score) =
+                play_seeded_game(entrants, bottom, top, &config).calculate_score();
+            (top_score, bottom_score)
+        };
+        scores = (
+            scores.0.saturating_add(top_score),
+            scores.1.saturating_add(bottom_score),
+        );
+        match Winner::from_scores((top_score, bottom_score)) {
+            Winner::P1 => wins.0 += 1,
+            Winner::P2 => wins.1 += 1,
+            Winner::Draw => {}
+        }
+    }
+
+    let winner = if (wins.1, scores.1) > (wins.0, scores.0) {
+        bottom
+    } else {
+        top
+    };
+    let played = KnockoutMatch {
+        top: entrants[top].name(),
+        bottom: Some(entrants[bottom].name()),
+        wins,
+        scores,
+        winner: entrants[winner].name(),
+    };
+    (played, winner)
+}
+
+/// The seeds, counting from 0, in the order they are placed in a bracket big enough for
+/// `entrants` entrants. Neighbouring pairs meet in the first round, and in each pair the seeds
+/// add up to one less than the size of the bracket, so the best seed meets the worst. Seeds of
+/// `entrants` or more are byes.
+fn bracket_order(entrants: usize) -> Vec<usize> {
+    let mut order = vec![0];
+    while order.len() < entrants {
+        let size = 2 * order.len();
+        order = order
+            .into_iter()
+            .flat_map(|seed| [seed, size - 1 - seed])
+            .collect();
+    }
+    order
+}
@@ -0,0 +1,948 @@
+//! Plays different strategies of the repeated prisoners dilemma against each other
+//! as inspired by this veritasium video https://www.youtube.com/watch?v=mScpHTIi-kM
+
+/// The number of rounds that the prisoners' dilemma will be repeated unless another number is chosen.
+pub const NUM_TURNS: usize = 200;
+
+/// The payout that both players get when they cooperate
+pub const COOPERATE_PAYOUT: isize = 10;
+
+/// The payout that both players get when they defect
+pub const DEFECT_PAYOUT: isize = 2;
+
+/// The payout you get when you narc out your opponent
+pub const NARC_OUT_OPPONENT_PAYOUT: isize = 20;
+
+/// The payout you get when your opponent narcs you out
+pub const GOT_NARCED_OUT_PAYOUT: isize = -5;
+
+/// The two strategies in the single prisoners' dilemma.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CooperateOrDefect {
+    Cooperate,
+    Defect,
+}
+
+use std::{fmt, marker::PhantomData};
+
+use CooperateOrDefect::*;
+
+impl CooperateOrDefect {
+    /// The single letter used for the move when printing long games, `C` or `D`.
+    pub fn as_char(&self) -> char {
+        match self {
+            Cooperate => 'C',
+            Defect => 'D',
+        }
+    }
+
+    /// The other move.
+    pub fn opposite(&self) -> CooperateOrDefect {
+        match self {
+            Cooperate => Defect,
+            Defect => Cooperate,
+        }
+    }
+}
+
+impl fmt::Display for CooperateOrDefect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_char())
+    }
+}
+
+/// What happened in a single round, named from player 1's point of view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    /// Both players cooperated
+    MutualCooperation,
+    /// Player 1 cooperated and player 2 defected
+    P1Exploited,
+    /// Player 1 defected and player 2 cooperated
+    P2Exploited,
+    /// Both players defected
+    MutualDefection,
+}
+
+impl Outcome {
+    /// All four outcomes.
+    pub const ALL: [Outcome; 4] = [
+        Outcome::MutualCooperation,
+        Outcome::P1Exploited,
+        Outcome::P2Exploited,
+        Outcome::MutualDefection,
+    ];
+}
+
+/// The outcome of a round in which player 1 played `p1` and player 2 played `p2`.
+pub fn outcome(p1: CooperateOrDefect, p2: CooperateOrDefect) -> Outcome {
+    match (p1, p2) {
+        (Cooperate, Cooperate) => Outcome::MutualCooperation,
+        (Cooperate, Defect) => Outcome::P1Exploited,
+        (Defect, Cooperate) => Outcome::P2Exploited,
+        (Defect, Defect) => Outcome::MutualDefection,
+    }
+}
+
+mod analysis;
+mod builder;
+mod cli;
+mod config;
+mod elo;
+mod evolution;
+mod experiment;
+mod field;
+mod fingerprint;
+mod fsm;
+mod interactive;
+mod json;
+mod knockout;
+mod n_player;
+#[cfg(feature = "packed-history")]
+mod packed;
+mod payoffs;
+mod record;
+mod registry;
+mod report;
+mod rng;
+mod snapshot;
+mod stats;
+mod strategies;
+mod testing;
+mod tournament;
+#[cfg(feature = "wasm")]
+mod wasm;
+
+pub use analysis::*;
+pub use builder::*;
+pub use cli::*;
+pub use config::*;
+pub use elo::*;
+pub use evolution::*;
+pub use experiment::*;
+pub use field::*;
+pub use fingerprint::*;
+pub use fsm::*;
+pub use interactive::*;
+pub use json::*;
+pub use knockout::*;
+pub use n_player::*;
+#[cfg(feature = "packed-history")]
+pub use packed::*;
+pub use payoffs::*;
+pub use record::*;
+pub use registry::*;
+pub use report::*;
+pub use rng::*;
+pub use snapshot::*;
+pub use stats::*;
+pub use strategies::*;
+pub use testing::*;
+pub use tournament::*;
+#[cfg(feature = "wasm")]
+pub use wasm::*;
+
+/// A strategy that a player will follow when playing the repeated prisoners' dilemma
+/// against the same player.
+pub trait Strategy {
+    const NAME: &'static str;
+
+    /// A one line summary of how the strategy plays, for listings and reports.
+    const DESCRIPTION: &'static str = "";
+
+    /// Who came up with the strategy, if known.
+    const AUTHOR: &'static str = "";
+
+    /// Whether the strategy always makes the same move given the same history. Strategies that
+    /// make random choices set this to `false`.
+    const DETERMINISTIC: bool = true;
+
+    /// Any memory the strategy needs beyond the move histories. A fresh state is created at the
+    /// beginning of each game. Strategies that only look at the histories use `()`, and ones
+    /// that need memory lasting from one game to the next implement
+    /// [`FieldStrategy`] instead.
+    type State: Default;
+
+    /// Calculate your strategy (cooperate or defect) in the next iteration of the repeated prisoners' dilemma.
+    ///
+    /// Assumes that the slices are the same length.
+    fn next_move(
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+        state: &mut Self::State,
+    ) -> CooperateOrDefect;
+
+    /// Calculate your next move from a [`HistoryView`], which along with the histories carries
+    /// running totals that the game keeps up to date. Games call this rather than
+    /// [`next_move`](Self::next_move), so strategies that would otherwise rescan the whole
+    /// history every round can override it to decide in constant time. Overrides must choose
+    /// the same move that `next_move` would.
+    fn next_move_with_history(
+        history: &HistoryView<'_>,
+        state: &mut Self::State,
+    ) -> CooperateOrDefect {
+        Self::next_move(history.my_moves, history.their_moves, state)
+    }
+}
+
+/// An object-safe version of [`Strategy`], for players that are chosen at runtime or that carry
+/// their own configuration. Because the player is a value, it keeps any state it needs in
+/// `self`.
+///
+/// Every `Strategy` can be used as a `DynStrategy` by wrapping it in a [`StrategyInstance`].
+pub trait DynStrategy {
+    /// The name of the strategy, as shown in tournament results.
+    fn name(&self) -> &str;
+
+    /// A one line summary of how the strategy plays, for listings and reports.
+    fn description(&self) -> &str {
+        ""
+    }
+
+    /// Who came up with the strategy, if known.
+    fn author(&self) -> &str {
+        ""
+    }
+
+    /// Whether the player always makes the same move given the same history, so that its games
+    /// come out the same whatever seed it was created with. Players that make random choices
+    /// return `false`.
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+
+    /// Calculate your strategy (cooperate or defect) in the next iteration of the repeated prisoners' dilemma.
+    ///
+    /// Assumes that the slices are the same length.
+    fn next_move(
+        &mut self,
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+    ) -> CooperateOrDefect;
+
+    /// Calculate your next move from a [`HistoryView`], see [`Strategy::next_move_with_history`].
+    fn next_move_with_history(&mut self, history: &HistoryView<'_>) -> CooperateOrDefect {
+        self.next_move(history.my_moves, history.their_moves)
+    }
+
+    /// Calculate your next move, or explain why you could not, for players that can fail while
+    /// the game is running, such as ones loaded from plugins. Games call this rather than
+    /// [`next_move_with_history`](Self::next_move_with_history), which it calls by default.
+    ///
+    /// A player that fails forfeits the round: the game plays `Defect` on its behalf and records
+    /// the error, see [`RepeatedPrisonersDilemma::failures`].
+    fn try_next_move(
+        &mut self,
+        history: &HistoryView<'_>,
+    ) -> Result<CooperateOrDefect, StrategyError> {
+        Ok(self.next_move_with_history(history))
+    }
+}
+
+/// Why a player could not choose its next move, see [`DynStrategy::try_next_move`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrategyError(pub String);
+
+impl fmt::Display for StrategyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for StrategyError {}
+
+impl DynStrategy for Box<dyn DynStrategy> {
+    fn name(&self) -> &str {
+        self.as_ref().name()
+    }
+
+    fn description(&self) -> &str {
+        self.as_ref().description()
+    }
+
+    fn author(&self) -> &str {
+        self.as_ref().author()
+    }
+
+    fn is_deterministic(&self) -> bool {
+        self.as_ref().is_deterministic()
+    }
+
+    fn next_move(
+        &mut self,
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+    ) -> CooperateOrDefect {
+        self.as_mut().next_move(my_moves, their_moves)
+    }
+
+    fn next_move_with_history(&mut self, history: &HistoryView<'_>) -> CooperateOrDefect {
+        self.as_mut().next_move_with_history(history)
+    }
+
+    fn try_next_move(
+        &mut self,
+        history: &HistoryView<'_>,
+    ) -> Result<CooperateOrDefect, StrategyError> {
+        self.as_mut().try_next_move(history)
+    }
+}
+
+/// A player following the `Strategy` `S`, together with the state it keeps during one game.
+pub struct StrategyInstance<S: Strategy> {
+    state: S::State,
+}
+
+impl<S: Strategy> StrategyInstance<S> {
+    pub fn new() -> Self {
+        Self {
+            state: S::State::default(),
+        }
+    }
+}
+
+impl<S> StrategyInstance<S>
+where
+    S: Strategy + 'static,
+    S::State: 'static,
+{
+    /// A fresh player for the strategy `S`, ready to be used anywhere a `DynStrategy` is needed.
+    pub fn boxed() -> Box<dyn DynStrategy> {
+        Box::new(Self::new())
+    }
+}
+
+impl<S: Strategy> Default for StrategyInstance<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Strategy> DynStrategy for StrategyInstance<S> {
+    fn name(&self) -> &str {
+        S::NAME
+    }
+
+    fn description(&self) -> &str {
+        S::DESCRIPTION
+    }
+
+    fn author(&self) -> &str {
+        S::AUTHOR
+    }
+
+    fn is_deterministic(&self) -> bool {
+        S::DETERMINISTIC
+    }
+
+    fn next_move(
+        &mut self,
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+    ) -> CooperateOrDefect {
+        S::next_move(my_moves, their_moves, &mut self.state)
+    }
+
+    fn next_move_with_history(&mut self, history: &HistoryView<'_>) -> CooperateOrDefect {
+        S::next_move_with_history(history, &mut self.state)
+    }
+}
+
+/// Everything a player knows when choosing its next move: the histories of both players, as the
+/// player saw them, and running totals over them.
+#[derive(Debug, Clone, Copy)]
+pub struct HistoryView<'a> {
+    pub my_moves: &'a [CooperateOrDefect],
+    pub their_moves: &'a [CooperateOrDefect],
+    /// The number of times this player has defected so far
+    pub my_defections: usize,
+    /// The number of times the opponent has defected so far
+    pub their_defections: usize,
+}
+
+impl<'a> HistoryView<'a> {
+    /// A view of the given histories, counting up the totals from scratch. Games keep the
+    /// totals as they go instead, so this is for calling strategies outside of a game.
+    pub fn new(my_moves: &'a [CooperateOrDefect], their_moves: &'a [CooperateOrDefect]) -> Self {
+        let defections =
+            |moves: &[CooperateOrDefect]| moves.iter().filter(|&&m| m == Defect).count();
+        Self {
+            my_moves,
+            their_moves,
+            my_defections: defections(my_moves),
+            their_defections: defections(their_moves),
+        }
+    }
+
+    /// The number of rounds played so far.
+    pub fn rounds(&self) -> usize {
+        self.my_moves.len()
+    }
+
+    /// The number of times the opponent has cooperated so far.
+    pub fn their_cooperations(&self) -> usize {
+        self.their_moves.len() - self.their_defections
+    }
+
+    pub fn my_last_move(&self) -> Option<CooperateOrDefect> {
+        self.my_moves.last().copied()
+    }
+
+    pub fn their_last_move(&self) -> Option<CooperateOrDefect> {
+        self.their_moves.last().copied()
+    }
+
+    /// Both players' moves over the last `rounds` rounds, or over the whole game if fewer have
+    /// been played.
+    pub fn recent(&self, rounds: usize) -> (&'a [CooperateOrDefect], &'a [CooperateOrDefect]) {
+        let start = self.my_moves.len().saturating_sub(rounds);
+        (&self.my_moves[start..], &self.their_moves[start..])
+    }
+}
+
+/// One of the two players in a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Player {
+    One,
+    Two,
+}
+
+/// A round in which a player failed to choose a move and forfeited, see
+/// [`RepeatedPrisonersDilemma::failures`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrategyFailure {
+    pub player: Player,
+    /// The round that was forfeited, counting from 1
+    pub round: usize,
+    pub error: StrategyError,
+}
+
+/// An instance of the repeated prisoners' dilemma. The same two players play against each other
+/// for several rounds. In each round they are able to choose whether to cooperate or defect, and they
+/// have knowledge of the entire history of the game.
+///
+/// The type parameters name the strategies of the two players. Games between players that are
+/// only known at runtime use [`Dynamic`] for both, see [`DynGame`].
+pub struct RepeatedPrisonersDilemma<P1, P2> {
+    // Hopefully in the wasm-friendly future, we can make
+    // the strategies wasm blobs that are instances instead of type parameters??
+    /// Player1, whose type is `P1` unless the game is a `DynGame`
+    player_1: Box<dyn DynStrategy>,
+    /// Player2, whose type is `P2` unless the game is a `DynGame`
+    player_2: Box<dyn DynStrategy>,
+    /// History of player1's moves
+    player_1_moves: Vec<CooperateOrDefect>,
+    ///History of player2's moves
+    player_2_moves: Vec<CooperateOrDefect>,
+    /// The payouts used to score each round
+    payoffs: AsymmetricPayoffs,
+    /// Player1's total payout so far, kept up to date after every round
+    player_1_score: isize,
+    /// Player2's total payout so far, kept up to date after every round
+    player_2_score: isize,
+    /// The probability that a player's move is flipped to the opposite one before it is played
+    noise: f64,
+    /// Decides which moves are flipped by the noise
+    rng: Rng,
+    /// Player1's possibly mistaken view of player2's moves
+    player_1_view: Vec<CooperateOrDefect>,
+    /// Player2's possibly mistaken view of player1's moves
+    player_2_view: Vec<CooperateOrDefect>,
+    /// The number of times player1 has defected
+    player_1_defections: usize,
+    /// The number of times player2 has defected
+    player_2_defections: usize,
+    /// The number of defections in player1's view of player2's moves
+    player_1_view_defections: usize,
+    /// The number of defections in player2's view of player1's moves
+    player_2_view_defections: usize,
+    /// The probability that a player sees its opponent's move as the opposite one
+    perception_noise: f64,
+    /// Decides which moves are misperceived
+    perception_rng: Rng,
+    /// Decides when games with a random horizon end
+    horizon_rng: Rng,
+    /// Every round that a player forfeited because it failed to choose a move
+    failures: Vec<StrategyFailure>,
+    _ph_data: PhantomData<(P1, P2)>,
+}
+
+/// Stands in for the strategy type parameters of a game whose players are chosen at runtime.
+pub enum Dynamic {}
+
+/// A game between two players that are chosen at runtime, such as from a list of strategies.
+pub type DynGame = RepeatedPrisonersDilemma<Dynamic, Dynamic>;
+
+impl<P1, P2> RepeatedPrisonersDilemma<P1, P2>
+where
+    P1: Strategy + 'static,
+    P2: Strategy + 'static,
+    P1::State: 'static,
+    P2::State: 'static,
+{
+    pub fn new() -> Self {
+        Self::with_payoffs(PayoffMatrix::default())
+    }
+
+    /// Create a game that is scored with the given payoffs instead of the default ones.
+    pub fn with_payoffs(payoffs: PayoffMatrix) -> Self {
+        Self::with_asymmetric_payoffs(AsymmetricPayoffs::symmetric(payoffs))
+    }
+
+    /// Create a game in which each player is paid from its own payoff matrix.
+    pub fn with_asymmetric_payoffs(payoffs: AsymmetricPayoffs) -> Self {
+        Self::from_players(
+            StrategyInstance::<P1>::boxed(),
+            StrategyInstance::<P2>::boxed(),
+            payoffs,
+        )
+    }
+}
+
+impl<P1, P2> Default for RepeatedPrisonersDilemma<P1, P2>
+where
+    P1: Strategy + 'static,
+    P2: Strategy + 'static,
+    P1::State: 'static,
+    P2::State: 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DynGame {
+    pub fn new(player_1: Box<dyn DynStrategy>, player_2: Box<dyn DynStrategy>) -> Self {
+        Self::with_payoffs(player_1, player_2, PayoffMatrix::default())
+    }
+
+    /// Create a game that is scored with the given payoffs instead of the default ones.
+    pub fn with_payoffs(
+        player_1: Box<dyn DynStrategy>,
+        player_2: Box<dyn DynStrategy>,
+        payoffs: PayoffMatrix,
+    ) -> Self {
+        Self::with_asymmetric_payoffs(player_1, player_2, AsymmetricPayoffs::symmetric(payoffs))
+    }
+
+    /// Create a game in which each player is paid from its own payoff matrix.
+    pub fn with_asymmetric_payoffs(
+        player_1: Box<dyn DynStrategy>,
+        player_2: Box<dyn DynStrategy>,
+        payoffs: AsymmetricPayoffs,
+    ) -> Self {
+        Self::from_players(player_1, player_2, payoffs)
+    }
+}
+
+impl<P1, P2> RepeatedPrisonersDilemma<P1, P2> {
+    fn from_players(
+        player_1: Box<dyn DynStrategy>,
+        player_2: Box<dyn DynStrategy>,
+        payoffs: AsymmetricPayoffs,
+    ) -> Self {
+        Self {
+            player_1,
+            player_2,
+            player_1_moves: Vec::new(),
+            player_2_moves: Vec::new(),
+            payoffs,
+            player_1_score: 0,
+            player_2_score: 0,
+            noise: 0.0,
+            rng: Rng::new(0),
+            player_1_view: Vec::new(),
+            player_2_view: Vec::new(),
+            player_1_defections: 0,
+            player_2_defections: 0,
+            player_1_view_defections: 0,
+            player_2_view_defections: 0,
+            perception_noise: 0.0,
+            perception_rng: Rng::new(0),
+            horizon_rng: Rng::new(0),
+            failures: Vec::new(),
+            _ph_data: PhantomData,
+        }
+    }
+
+    /// Make the players' hands tremble: after each player picks a move, it is flipped to the
+    /// opposite move with probability `noise`. The flipped move is the one that is recorded,
+    /// scored, and shown to the opponent, and the player is never told that it was changed.
+    ///
+    /// The flips are decided by a generator seeded with `seed`, so noisy games can be replayed.
+    pub fn set_noise(&mut self, noise: f64, seed: u64) {
+        self.noise = noise;
+        self.rng = Rng::new(seed);
+    }
+
+    /// Make the players misread each other: each time a move is played, each player sees its
+    /// opponent's move as the opposite one with probability `noise`. Unlike [`Self::set_noise`],
+    /// the moves that were really played are left alone and are what the game is scored on; only
+    /// the history passed to each player's `next_move` is corrupted, and each player's view is
+    /// corrupted independently.
+    pub fn set_perception_noise(&mut self, noise: f64, seed: u64) {
+        self.perception_noise = noise;
+        self.perception_rng = Rng::new(seed);
+    }
+
+    /// Seed the generator that decides how long [`Self::play_random_horizon`] plays for.
+    pub fn set_horizon_seed(&mut self, seed: u64) {
+        self.horizon_rng = Rng::new(seed);
+    }
+
+    /// The names of player 1's and player 2's strategies.
+    pub fn names(&self) -> (&str, &str) {
+        (self.player_1.name(), self.player_2.name())
+    }
+
+    /// History of player 1's moves so far.
+    pub fn player_1_moves(&self) -> &[CooperateOrDefect] {
+        &self.player_1_moves
+    }
+
+    /// History of player 2's moves so far.
+    pub fn player_2_moves(&self) -> &[CooperateOrDefect] {
+        &self.player_2_moves
+    }
+
+    /// Play one more round, returning the moves that player 1 and player 2 played. Each player
+    /// sees its own moves and its opponent's, so a reactive strategy works the same in either
+    /// seat:
+    ///
+    /// ```
+    /// # use prisoners_dilemma_competition::{
+    /// #     AlwaysDefect, CooperateOrDefect::*, RepeatedPrisonersDilemma, TitForTat,
+    /// # };
+    /// let mut game = RepeatedPrisonersDilemma::<AlwaysDefect, TitForTat>::new();
+    /// game.play_n_rounds(3);
+    /// assert_eq!(game.player_2_moves(), [Cooperate, Defect, Defect]);
+    /// ```
+    pub fn play_next_round(&mut self) -> (CooperateOrDefect, CooperateOrDefect) {
+        // Strategies assume that both histories they are given are the same length
+        debug_assert_eq!(self.player_1_moves.len(), self.player_1_view.len());
+        let p1_choice = self.player_1.try_next_move(&HistoryView {
+            my_moves: &self.player_1_moves,
+            their_moves: &self.player_1_view,
+            my_defections: self.player_1_defections,
+            their_defections: self.player_1_view_defections,
+        });
+        debug_assert_eq!(self.player_2_moves.len(), self.player_2_view.len());
+        let p2_choice = self.player_2.try_next_move(&HistoryView {
+            my_moves: &self.player_2_moves,
+            their_moves: &self.player_2_view,
+            my_defections: self.player_2_defections,
+            their_defections: self.player_2_view_defections,
+        });
+
+        let round = self.player_1_moves.len() + 1;
+        let p1_move = self.forfeit_on_failure(Player::One, round, p1_choice);
+        let p2_move = self.forfeit_on_failure(Player::Two, round, p2_choice);
+
+        let p1_move = self.apply_noise(p1_move);
+        let p2_move = self.apply_noise(p2_move);
+
+        let p1_view = self.perceive(&p2_move);
+        let p2_view = self.perceive(&p1_move);
+        self.player_1_view.push(p1_view);
+        self.player_2_view.push(p2_view);
+        self.player_1_view_defections += usize::from(p1_view == Defect);
+        self.player_2_view_defections += usize::from(p2_view == Defect);
+        self.player_1_defections += usize::from(p1_move == Defect);
+        self.player_2_defections += usize::from(p2_move == Defect);
+
+        let (p1_payout, p2_payout) = self.payoffs.payouts(&p1_move, &p2_move);
+        self.player_1_score = self.player_1_score.saturating_add(p1_payout);
+        self.player_2_score = self.player_2_score.saturating_add(p2_payout);
+
+        self.player_1_moves.push(p1_move);
+        self.player_2_moves.push(p2_move);
+
+        (p1_move, p2_move)
+    }
+
+    /// Play `rounds` more rounds, calling `observer` after each one with the round's index in
+    /// the histories (starting at 0) and the moves player 1 and player 2 played.
+    pub fn play_with_observer<F>(&mut self, rounds: usize, mut observer: F)
+    where
+        F: FnMut(usize, &CooperateOrDefect, &CooperateOrDefect),
+    {
+        for _ in 0..rounds {
+            let round = self.player_1_moves.len();
+            let (p1_move, p2_move) = self.play_next_round();
+            observer(round, &p1_move, &p2_move);
+        }
+    }
+
+    /// Play `rounds` more rounds, printing both players' moves after each one.
+    pub fn play_verbose(&mut self, rounds: usize) {
+        self.play_with_observer(rounds, |_, p1_move, p2_move| {
+            println!("({}, {})", p1_move, p2_move)
+        });
+    }
+
+    /// An endless iterator over the rounds of the game that plays each round only when it is
+    /// asked for, yielding the moves player 1 and player 2 played. Use [`Iterator::take`] to
+    /// stop after a given number of rounds.
+    pub fn rounds(&mut self) -> Rounds<'_, P1, P2> {
+        Rounds { game: self }
+    }
+
+    /// Every round so far that a player forfeited because it failed to choose a move, in the
+    /// order they happened. A forfeited round is played as a defection.
+    pub fn failures(&self) -> &[StrategyFailure] {
+        &self.failures
+    }
+
+    /// The move the player chose, or a defection if it failed to choose one, in which case the
+    /// failure is recorded.
+    fn forfeit_on_failure(
+        &mut self,
+        player: Player,
+        round: usize,
+        choice: Result<CooperateOrDefect, StrategyError>,
+    ) -> CooperateOrDefect {
+        choice.unwrap_or_else(|error| {
+            self.failures.push(StrategyFailure {
+                player,
+                round,
+                error,
+            });
+            Defect
+        })
+    }
+
+    /// How a player sees the move its opponent really played.
+    fn perceive(&mut self, actual: &CooperateOrDefect) -> CooperateOrDefect {
+        match (actual, self.perception_rng.chance(self.perception_noise)) {
+            (Cooperate, false) | (Defect, true) => Cooperate,
+            (Defect, false) | (Cooperate, true) => Defect,
+        }
+    }
+
+    fn apply_noise(&mut self, intended: CooperateOrDefect) -> CooperateOrDefect {
+        if self.rng.chance(self.noise) {
+            intended.opposite()
+        } else {
+            intended
+        }
+    }
+
+    /// Play `rounds` more rounds of the game.
+    pub fn play_n_rounds(&mut self, rounds: usize) {
+        for _ in 0..rounds {
+            self.play_next_round();
+        }
+    }
+
+    /// Play until the game ends at random, without anyone knowing in advance which round is the
+    /// last. At least one round is played, and after each round the game continues with
+    /// probability `w`, so the number of rounds follows a geometric distribution with mean
+    /// `1 / (1 - w)`. Returns the number of rounds that were played.
+    ///
+    /// The length is decided by a generator seeded with [`Self::set_horizon_seed`], so the same
+    /// seed always gives the same length.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `w` is at least 0 and less than 1.
+    pub fn play_random_horizon(&mut self, w: f64) -> usize {
+        assert!(
+            (0.0..1.0).contains(&w),
+            "the continuation probability must be in [0, 1), found {w}"
+        );
+
+        let mut rounds = 0;
+        loop {
+            self.play_next_round();
+            rounds += 1;
+            if !self.horizon_rng.chance(w) {
+                return rounds;
+            }
+        }
+    }
+
+    /// Play a game of `rounds` rounds in total, counting any already played, but stop as soon as
+    /// the winner is decided: when one player is further ahead than the other could catch up
+    /// with in the rounds that are left, even by exploiting it in every one of them.
+    ///
+    /// Returns the winner and the number of rounds that had been played when the win was
+    /// clinched, or `None` if the game was played to the end and ended in a tie.
+    pub fn play_until_decided(&mut self, rounds: usize) -> Option<(Player, usize)> {
+        // The most that the gap between the scores can change in one round
+        let swing = Outcome::ALL
+            .into_iter()
+            .map(|outcome| {
+                let (p1_payout, p2_payout) = self.payoffs.outcome_payouts(outcome);
+                (p1_payout as i128 - p2_payout as i128).abs()
+            })
+            .max()
+            .unwrap_or_default();
+
+        loop {
+            let played = self.player_1_moves.len();
+            let (p1_score, p2_score) = self.calculate_score();
+            let lead = p1_score as i128 - p2_score as i128;
+            if lead.abs() > rounds.saturating_sub(played) as i128 * swing {
+                let winner = if lead > 0 { Player::One } else { Player::Two };
+                return Some((winner, played));
+            }
+            if played >= rounds {
+                return None;
+            }
+            self.play_next_round();
+        }
+    }
+
+    /// Play the default number of rounds, `NUM_TURNS`.
+    pub fn play(&mut self) {
+        self.play_n_rounds(NUM_TURNS);
+    }
+
+    /// The total payout each player has earned so far, which is `(0, 0)` before the first round.
+    ///
+    /// Totals saturate at `isize::MAX` (or `isize::MIN`) rather than overflowing, so very long
+    /// games with large payouts never panic or wrap around.
+    ///
+    /// ```
+    /// # use prisoners_dilemma_competition::*;
+    /// let payoffs = PayoffMatrix {
+    ///     reward: isize::MAX / 2,
+    ///     temptation: isize::MAX,
+    ///     punishment: 0,
+    ///     sucker: isize::MIN,
+    /// };
+    /// let mut game =
+    ///     RepeatedPrisonersDilemma::<AlwaysCooperate, AlwaysCooperate>::with_payoffs(payoffs);
+    /// game.play_n_rounds(3);
+    /// assert_eq!(game.calculate_score(), (isize::MAX, isize::MAX));
+    /// ```
+    pub fn calculate_score(&self) -> (isize, isize) {
+        (self.player_1_score, self.player_2_score)
+    }
+
+    /// The outcome of each round played so far.
+    pub fn outcomes(&self) -> Vec<Outcome> {
+        self.player_1_moves
+            .iter()
+            .zip(&self.player_2_moves)
+            .map(|(&p1_move, &p2_move)| outcome(p1_move, p2_move))
+            .collect()
+    }
+
+    /// The payout each player earned in each round played so far, one pair per round.
+    pub fn round_payoffs(&self) -> Vec<(isize, isize)> {
+        self.player_1_moves
+            .iter()
+            .zip(&self.player_2_moves)
+            .map(|(p1_move, p2_move)| self.payoffs.payouts(p1_move, p2_move))
+            .collect()
+    }
+
+    /// The average payout per round that each player has earned so far, for comparing games of
+    /// different lengths. Both averages are `0.0` before any rounds have been played.
+    pub fn normalized_score(&self) -> (f64, f64) {
+        let rounds = self.player_1_moves.len();
+        if rounds == 0 {
+            return (0.0, 0.0);
+        }
+        let (p1_score, p2_score) = self.calculate_score();
+        (
+            p1_score as f64 / rounds as f64,
+            p2_score as f64 / rounds as f64,
+        )
+    }
+
+    /// The total payout each player has earned so far, with the payout from round `t` (counting
+    /// from 0) weighted by `delta` to the power `t`. A `delta` of 1 gives the same totals as
+    /// [`calculate_score`](Self::calculate_score).
+    ///
+    /// The sums use compensated (Kahan) summation so that long games with `delta` close to 1 do
+    /// not lose precision as many small weighted payouts are added to a large total.
+    pub fn calculate_discounted_score(&self, delta: f64) -> (f64, f64) {
+        let mut p1_total = CompensatedSum::default();
+        let mut p2_total = CompensatedSum::default();
+        let mut weight = 1.0;
+
+        for (p1_payout, p2_payout) in self.round_payoffs() {
+            p1_total.add(weight * p1_payout as f64);
+            p2_total.add(weight * p2_payout as f64);
+            weight *= delta;
+        }
+
+        (p1_total.sum, p2_total.sum)
+    }
+
+    /// Export the rounds played so far as CSV, one row per round. Moves are written as `C` or `D`
+    /// and the last two columns hold each player's running score. Before any rounds have been
+    /// played only the header row is written.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("round,p1_move,p2_move,p1_cumulative,p2_cumulative\n");
+        let (mut p1_total, mut p2_total): (isize, isize) = (0, 0);
+
+        let moves = self.player_1_moves.iter().zip(&self.player_2_moves);
+        for (round, ((p1_move, p2_move), (p1_payout, p2_payout))) in
+            moves.zip(self.round_payoffs()).enumerate()
+        {
+            p1_total = p1_total.saturating_add(p1_payout);
+            p2_total = p2_total.saturating_add(p2_payout);
+
+            csv += &format!(
+                "{},{},{},{},{}\n",
+                round + 1,
+                p1_move,
+                p2_move,
+                p1_total,
+                p2_total
+            );
+        }
+
+        csv
+    }
+}
+
+/// Lazily plays a game one round at a time, see [`RepeatedPrisonersDilemma::rounds`].
+pub struct Rounds<'a, P1, P2> {
+    game: &'a mut RepeatedPrisonersDilemma<P1, P2>,
+}
+
+impl<P1, P2> Iterator for Rounds<'_, P1, P2> {
+    type Item = (CooperateOrDefect, CooperateOrDefect);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.game.play_next_round())
+    }
+}
+
+/// Total up the payouts for both players over a history of moves, saturating instead of
+/// overflowing.
+pub(crate) fn score(
+    payoffs: &AsymmetricPayoffs,
+    player_1_moves: &[CooperateOrDefect],
+    player_2_moves: &[CooperateOrDefect],
+) -> (isize, isize) {
+    player_1_moves
+        .iter()
+        .zip(player_2_moves)
+        .map(|(&p1_move, &p2_move)| outcome(p1_move, p2_move))
+        .fold((0, 0), |(p1, p2), outcome| {
+            let (p1_payout, p2_payout) = payoffs.outcome_payouts(outcome);
+            (p1.saturating_add(p1_payout), p2.saturating_add(p2_payout))
+        })
+}
+
+/// A running total that keeps track of the rounding error lost in each addition and feeds it
+/// back into the next one.
+#[derive(Default)]
+struct CompensatedSum {
+    sum: f64,
+    compensation: f64,
+}
+
+impl CompensatedSum {
+    fn add(&mut self, value: f64) {
+        let corrected = value - self.compensation;
+        let sum = self.sum + corrected;
+        self.compensation = (sum - self.sum) - corrected;
+        self.sum = sum;
+    }
+}
@@ -0,0 +1,165 @@
+//! A repeated dilemma between any number of players, in the style of a public goods game: in
+//! each round every player chooses whether to contribute to a shared pot, and everyone benefits
+//! from the pot whether or not they contributed.
+
+use crate::{AlwaysCooperate, AlwaysDefect, CooperateOrDefect, CooperateOrDefect::*};
+
+/// A strategy for a game with any number of players.
+pub trait NPlayerStrategy {
+    /// The name of the strategy, as shown in results.
+    fn name(&self) -> &str;
+
+    /// Calculate your move in the next round. `histories` holds every player's moves so far,
+    /// in seating order, and `me` is the index of your own history in it.
+    ///
+    /// Assumes that all of the histories are the same length.
+    fn next_move(&mut self, me: usize, histories: &[Vec<CooperateOrDefect>]) -> CooperateOrDefect;
+}
+
+impl NPlayerStrategy for AlwaysCooperate {
+    fn name(&self) -> &str {
+        "Always Cooperate"
+    }
+
+    fn next_move(
+        &mut self,
+        _me: usize,
+        _histories: &[Vec<CooperateOrDefect>],
+    ) -> CooperateOrDefect {
+        Cooperate
+    }
+}
+
+impl NPlayerStrategy for AlwaysDefect {
+    fn name(&self) -> &str {
+        "Always Defect"
+    }
+
+    fn next_move(
+        &mut self,
+        _me: usize,
+        _histories: &[Vec<CooperateOrDefect>],
+    ) -> CooperateOrDefect {
+        Defect
+    }
+}
+
+/// How an N-player round is scored. Every cooperator pays `cost` into a pot, the pot is
+/// multiplied by `multiplier`, and the result is shared equally between all of the players.
+///
+/// With a multiplier between 1 and the number of players this is a dilemma: each contribution
+/// returns less than it cost to the player who made it, but more in total to the group.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PublicGoods {
+    pub cost: f64,
+    pub multiplier: f64,
+}
+
+impl Default for PublicGoods {
+    fn default() -> Self {
+        Self {
+            cost: 1.0,
+            multiplier: 3.0,
+        }
+    }
+}
+
+impl PublicGoods {
+    /// The payout to a player who made `my_move` in a round with `cooperators` cooperators out
+    /// of `players` players.
+    pub fn payout(&self, my_move: &CooperateOrDefect, cooperators: usize, players: usize) -> f64 {
+        let share = self.cost * self.multiplier * cooperators as f64 / players as f64;
+        match my_move {
+            Cooperate => share - self.cost,
+            Defect => share,
+        }
+    }
+}
+
+/// A repeated public goods game between any number of players. Every player sees the full
+/// history of everyone's moves.
+///
+/// A defector among cooperators shares in the pot without paying into it, so it earns the most:
+///
+/// ```
+/// # use prisoners_dilemma_competition::*;
+/// let mut game = NPlayerGame::new(
+///     vec![
+///         Box::new(AlwaysCooperate),
+///         Box::new(AlwaysCooperate),
+///         Box::new(AlwaysCooperate),
+///         Box::new(AlwaysDefect),
+///     ],
+///     PublicGoods::default(),
+/// );
+/// game.play_n_rounds(10);
+/// let scores = game.calculate_scores();
+/// // Each round the pot is 3 * 3 shared four ways, and each cooperator paid 1 into it
+/// assert_eq!(scores, [12.5, 12.5, 12.5, 22.5]);
+/// ```
+pub struct NPlayerGame {
+    players: Vec<Box<dyn NPlayerStrategy>>,
+    histories: Vec<Vec<CooperateOrDefect>>,
+    payoffs: PublicGoods,
+}
+
+impl NPlayerGame {
+    pub fn new(players: Vec<Box<dyn NPlayerStrategy>>, payoffs: PublicGoods) -> Self {
+        let histories = players.iter().map(|_| Vec::new()).collect();
+        Self {
+            players,
+            histories,
+            payoffs,
+        }
+    }
+
+    /// The names of the players' strategies, in seating order.
+    pub fn names(&self) -> Vec<&str> {
+        self.players.iter().map(|player| player.name()).collect()
+    }
+
+    /// Every player's moves so far, in seating order.
+    pub fn histories(&self) -> &[Vec<CooperateOrDefect>] {
+        &self.histories
+    }
+
+    /// Play one more round. Every player chooses its move before any of them are revealed.
+    pub fn play_next_round(&mut self) {
+        let moves = self
+            .players
+            .iter_mut()
+            .enumerate()
+            .map(|(me, player)| player.next_move(me, &self.histories))
+            .collect::<Vec<_>>();
+        for (history, next_move) in self.histories.iter_mut().zip(moves) {
+            history.push(next_move);
+        }
+    }
+
+    /// Play `rounds` more rounds of the game.
+    pub fn play_n_rounds(&mut self, rounds: usize) {
+        for _ in 0..rounds {
+            self.play_next_round();
+        }
+    }
+
+    /// The total payout each player has earned so far, in seating order.
+    pub fn calculate_scores(&self) -> Vec<f64> {
+        let players = self.players.len();
+        let mut scores = vec![0.0; players];
+        let rounds = self.histories.first().map_or(0, Vec::len);
+
+        for round in 0..rounds {
+            let cooperators = self
+                .histories
+                .iter()
+                .filter(|history| matches!(history[round], Cooperate))
+                .count();
+            for (score, history) in scores.iter_mut().zip(&self.histories) {
+                *score += self.payoffs.payout(&history[round], cooperators, players);
+            }
+        }
+
+        scores
+    }
+}
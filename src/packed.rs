@@ -0,0 +1,268 @@
+//! Histories stored one bit per move instead of one byte, for games of millions of rounds.
+//! Enabled by the `packed-history` feature.
+
+use crate::{
+    AsymmetricPayoffs, CooperateOrDefect, CooperateOrDefect::*, DynStrategy, HistoryView,
+    PayoffMatrix, Player, StrategyError, StrategyFailure,
+};
+
+/// A history of moves packed one bit per move, with a set bit for a defection.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackedMoves {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl PackedMoves {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push(&mut self, next_move: CooperateOrDefect) {
+        let (word, bit) = (self.len / 64, self.len % 64);
+        if bit == 0 {
+            self.words.push(0);
+        }
+        if next_move == Defect {
+            self.words[word] |= 1 << bit;
+        }
+        self.len += 1;
+    }
+
+    /// The move at position `index`, counting from 0, if there is one.
+    pub fn get(&self, index: usize) -> Option<CooperateOrDefect> {
+        (index < self.len).then(|| match self.words[index / 64] >> (index % 64) & 1 {
+            0 => Cooperate,
+            _ => Defect,
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = CooperateOrDefect> + '_ {
+        (0..self.len).map(|index| self.get(index).unwrap())
+    }
+
+    /// Expand the history back into one value per move.
+    pub fn unpack(&self) -> Vec<CooperateOrDefect> {
+        self.iter().collect()
+    }
+
+    /// The number of bytes of heap memory holding the moves.
+    pub fn heap_bytes(&self) -> usize {
+        self.words.capacity() * std::mem::size_of::<u64>()
+    }
+}
+
+impl FromIterator<CooperateOrDefect> for PackedMoves {
+    fn from_iter<I: IntoIterator<Item = CooperateOrDefect>>(moves: I) -> Self {
+        let mut packed = Self::new();
+        for next_move in moves {
+            packed.push(next_move);
+        }
+        packed
+    }
+}
+
+/// A game that keeps its histories packed, see [`PackedMoves`].
+///
+/// The players are only shown the last `window` rounds, as ordinary slices, so this is only
+/// suitable for strategies that never look further back than that and that do not depend on
+/// how many rounds have been played. For those strategies, such as Tit for Tat or Pavlov with a
+/// `window` of 1, it plays exactly the same game as
+/// [`RepeatedPrisonersDilemma`](crate::RepeatedPrisonersDilemma) in about an eighth of the
+/// memory:
+///
+/// ```
+/// # use prisoners_dilemma_competition::*;
+/// let rounds = 1 << 17;
+/// let mut game = DynGame::new(
+///     StrategyInstance::<TitForTat>::boxed(),
+///     StrategyInstance::<Pavlov>::boxed(),
+/// );
+/// game.play_n_rounds(rounds);
+/// let mut packed = PackedGame::new(
+///     StrategyInstance::<TitForTat>::boxed(),
+///     StrategyInstance::<Pavlov>::boxed(),
+///     1,
+/// );
+/// packed.play_n_rounds(rounds);
+///
+/// assert_eq!(packed.calculate_score(), game.calculate_score());
+/// assert_eq!(packed.player_1_moves().unpack(), game.player_1_moves());
+/// assert_eq!(packed.player_2_moves().unpack(), game.player_2_moves());
+///
+/// let unpacked_bytes = game.player_1_moves().len() * std::mem::size_of::<CooperateOrDefect>();
+/// let packed_bytes = packed.player_1_moves().heap_bytes();
+/// let ratio = unpacked_bytes as f64 / packed_bytes as f64;
+/// assert!((6.0..=10.0).contains(&ratio), "{ratio}");
+/// ```
+///
+/// A player that fails to choose a move forfeits the round by defecting, as in
+/// [`RepeatedPrisonersDilemma`](crate::RepeatedPrisonersDilemma), and the failure is recorded:
+///
+/// ```
+/// # use prisoners_dilemma_competition::{CooperateOrDefect::{self, *}, *};
+/// struct Broken;
+///
+/// impl DynStrategy for Broken {
+///     fn name(&self) -> &str {
+///         "Broken"
+///     }
+///
+///     fn next_move(
+///         &mut self,
+///         _my_moves: &[CooperateOrDefect],
+///         _their_moves: &[CooperateOrDefect],
+///     ) -> CooperateOrDefect {
+///         Cooperate
+///     }
+///
+///     fn try_next_move(
+///         &mut self,
+///         _history: &HistoryView<'_>,
+///     ) -> Result<CooperateOrDefect, StrategyError> {
+///         Err(StrategyError("out of order".into()))
+///     }
+/// }
+///
+/// let mut packed = PackedGame::new(Box::new(Broken), StrategyInstance::<TitForTat>::boxed(), 1);
+/// packed.play_n_rounds(3);
+/// let mut game = DynGame::new(Box::new(Broken), StrategyInstance::<TitForTat>::boxed());
+/// game.play_n_rounds(3);
+/// assert_eq!(packed.failures(), game.failures());
+/// assert_eq!(packed.calculate_score(), game.calculate_score());
+/// ```
+pub struct PackedGame {
+    player_1: Box<dyn DynStrategy>,
+    player_2: Box<dyn DynStrategy>,
+    player_1_moves: PackedMoves,
+    player_2_moves: PackedMoves,
+    /// The most recent moves of player1, at least `window` of them once there are that many
+    player_1_tail: Vec<CooperateOrDefect>,
+    /// The most recent moves of player2, at least `window` of them once there are that many
+    player_2_tail: Vec<CooperateOrDefect>,
+    /// How many of the most recent rounds the players are shown
+    window: usize,
+    payoffs: AsymmetricPayoffs,
+    player_1_score: isize,
+    player_2_score: isize,
+    failures: Vec<StrategyFailure>,
+}
+
+impl PackedGame {
+    pub fn new(
+        player_1: Box<dyn DynStrategy>,
+        player_2: Box<dyn DynStrategy>,
+        window: usize,
+    ) -> Self {
+        Self::with_payoffs(player_1, player_2, window, PayoffMatrix::default())
+    }
+
+    /// Create a game that is scored with the given payoffs instead of the default ones.
+    pub fn with_payoffs(
+        player_1: Box<dyn DynStrategy>,
+        player_2: Box<dyn DynStrategy>,
+        window: usize,
+        payoffs: PayoffMatrix,
+    ) -> Self {
+        Self {
+            player_1,
+            player_2,
+            player_1_moves: PackedMoves::new(),
+            player_2_moves: PackedMoves::new(),
+            player_1_tail: Vec::new(),
+            player_2_tail: Vec::new(),
+            window,
+            payoffs: AsymmetricPayoffs::symmetric(payoffs),
+            player_1_score: 0,
+            player_2_score: 0,
+            failures: Vec::new(),
+        }
+    }
+
+    pub fn player_1_moves(&self) -> &PackedMoves {
+        &self.player_1_moves
+    }
+
+    pub fn player_2_moves(&self) -> &PackedMoves {
+        &self.player_2_moves
+    }
+
+    /// Play one more round, returning the moves that player 1 and player 2 played.
+    pub fn play_next_round(&mut self) -> (CooperateOrDefect, CooperateOrDefect) {
+        let start = self.player_1_tail.len().saturating_sub(self.window);
+        let p1_tail = &self.player_1_tail[start..];
+        let p2_tail = &self.player_2_tail[start..];
+        let p1_choice = self
+            .player_1
+            .try_next_move(&HistoryView::new(p1_tail, p2_tail));
+        let p2_choice = self
+            .player_2
+            .try_next_move(&HistoryView::new(p2_tail, p1_tail));
+
+        let round = self.player_1_moves.len() + 1;
+        let p1_move = self.forfeit_on_failure(Player::One, round, p1_choice);
+        let p2_move = self.forfeit_on_failure(Player::Two, round, p2_choice);
+
+        let (p1_payout, p2_payout) = self.payoffs.payouts(&p1_move, &p2_move);
+        self.player_1_score = self.player_1_score.saturating_add(p1_payout);
+        self.player_2_score = self.player_2_score.saturating_add(p2_payout);
+
+        self.player_1_moves.push(p1_move);
+        self.player_2_moves.push(p2_move);
+        self.player_1_tail.push(p1_move);
+        self.player_2_tail.push(p2_move);
+        // Drop the moves that have scrolled out of the window every so often, rather than every
+        // round, so that keeping the tails costs a constant amount per round on average
+        if self.player_1_tail.len() >= 2 * self.window.max(1) {
+            let scrolled_out = self.player_1_tail.len() - self.window;
+            self.player_1_tail.drain(..scrolled_out);
+            self.player_2_tail.drain(..scrolled_out);
+        }
+
+        (p1_move, p2_move)
+    }
+
+    /// Play `rounds` more rounds of the game.
+    pub fn play_n_rounds(&mut self, rounds: usize) {
+        for _ in 0..rounds {
+            self.play_next_round();
+        }
+    }
+
+    /// The total payout each player has earned so far.
+    pub fn calculate_score(&self) -> (isize, isize) {
+        (self.player_1_score, self.player_2_score)
+    }
+
+    /// Every round so far that a player forfeited because it failed to choose a move, in the
+    /// order they happened. A forfeited round is played as a defection.
+    pub fn failures(&self) -> &[StrategyFailure] {
+        &self.failures
+    }
+
+    /// The move the player chose, or a defection if it failed to choose one, in which case the
+    /// failure is recorded.
+    fn forfeit_on_failure(
+        &mut self,
+        player: Player,
+        round: usize,
+        choice: Result<CooperateOrDefect, StrategyError>,
+    ) -> CooperateOrDefect {
+        choice.unwrap_or_else(|error| {
+            self.failures.push(StrategyFailure {
+                player,
+                round,
+                error,
+            });
+            Defect
+        })
+    }
+}
@@ -0,0 +1,315 @@
+//! The rewards handed out to each player after every round.
+
+use std::{fmt, str::FromStr};
+
+use crate::{
+    outcome, CooperateOrDefect, CooperateOrDefect::*, Outcome, COOPERATE_PAYOUT, DEFECT_PAYOUT,
+    GOT_NARCED_OUT_PAYOUT, NARC_OUT_OPPONENT_PAYOUT,
+};
+
+/// The payouts for each of the four possible outcomes of a single prisoners' dilemma, using the
+/// traditional names from the game theory literature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PayoffMatrix {
+    /// The payout that both players get when they cooperate
+    pub reward: isize,
+    /// The payout you get when you narc out your opponent
+    pub temptation: isize,
+    /// The payout that both players get when they defect
+    pub punishment: isize,
+    /// The payout you get when your opponent narcs you out
+    pub sucker: isize,
+}
+
+impl PayoffMatrix {
+    /// Create payoffs after checking that they make a genuine prisoners' dilemma: the payouts
+    /// must be ordered `temptation > reward > punishment > sucker`, and `2 * reward` must beat
+    /// `temptation + sucker` so that taking turns exploiting each other does not pay better
+    /// than cooperating. The first rule that is broken is reported.
+    ///
+    /// ```
+    /// # use prisoners_dilemma_competition::{PayoffError, PayoffMatrix};
+    /// assert!(PayoffMatrix::new(3, 5, 1, 0).is_ok());
+    /// assert_eq!(PayoffMatrix::new(5, 5, 1, 0), Err(PayoffError::TemptationNotAboveReward));
+    /// assert_eq!(PayoffMatrix::new(3, 5, 3, 0), Err(PayoffError::RewardNotAbovePunishment));
+    /// assert_eq!(PayoffMatrix::new(3, 5, 1, 1), Err(PayoffError::PunishmentNotAboveSucker));
+    /// assert_eq!(PayoffMatrix::new(3, 7, 1, 0), Err(PayoffError::AlternationPays));
+    /// ```
+    pub fn new(
+        reward: isize,
+        temptation: isize,
+        punishment: isize,
+        sucker: isize,
+    ) -> Result<Self, PayoffError> {
+        if temptation <= reward {
+            Err(PayoffError::TemptationNotAboveReward)
+        } else if reward <= punishment {
+            Err(PayoffError::RewardNotAbovePunishment)
+        } else if punishment <= sucker {
+            Err(PayoffError::PunishmentNotAboveSucker)
+        } else if 2 * reward as i128 <= temptation as i128 + sucker as i128 {
+            Err(PayoffError::AlternationPays)
+        } else {
+            Ok(Self {
+                reward,
+                temptation,
+                punishment,
+                sucker,
+            })
+        }
+    }
+
+    /// The payouts that player 1 and player 2 receive when they play the given moves.
+    pub fn payouts(
+        &self,
+        p1_move: &CooperateOrDefect,
+        p2_move: &CooperateOrDefect,
+    ) -> (isize, isize) {
+        self.outcome_payouts(outcome(*p1_move, *p2_move))
+    }
+
+    /// The payouts that player 1 and player 2 receive for a round with the given outcome.
+    pub fn outcome_payouts(&self, outcome: Outcome) -> (isize, isize) {
+        match outcome {
+            Outcome::MutualCooperation => (self.reward, self.reward),
+            Outcome::P1Exploited => (self.sucker, self.temptation),
+            Outcome::P2Exploited => (self.temptation, self.sucker),
+            Outcome::MutualDefection => (self.punishment, self.punishment),
+        }
+    }
+
+    /// The outcomes of the one-shot game in which neither player could do better by changing
+    /// only its own move.
+    pub fn nash_equilibria(&self) -> Vec<Outcome> {
+        let moves = [Cooperate, Defect];
+        let mut equilibria = Vec::new();
+        for p1_move in &moves {
+            for p2_move in &moves {
+                let (p1_payout, p2_payout) = self.payouts(p1_move, p2_move);
+                let p1_content = moves
+                    .iter()
+                    .all(|other| self.payouts(other, p2_move).0 <= p1_payout);
+                let p2_content = moves
+                    .iter()
+                    .all(|other| self.payouts(p1_move, other).1 <= p2_payout);
+                if p1_content && p2_content {
+                    equilibria.push(outcome(*p1_move, *p2_move));
+                }
+            }
+        }
+        equilibria
+    }
+
+    /// Check whether these payoffs really make a prisoners' dilemma, so that custom payoffs can
+    /// be checked before running a tournament with them.
+    pub fn classify(&self) -> StageGame {
+        let Self {
+            reward,
+            temptation,
+            punishment,
+            sucker,
+        } = *self;
+
+        if !(temptation > reward && reward > punishment && punishment > sucker) {
+            StageGame::NotADilemma {
+                equilibria: self.nash_equilibria(),
+            }
+        } else if 2 * reward as i128 > temptation as i128 + sucker as i128 {
+            StageGame::PrisonersDilemma
+        } else {
+            StageGame::AlternationPays
+        }
+    }
+}
+
+/// Why some payoffs do not make a prisoners' dilemma, see [`PayoffMatrix::new`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayoffError {
+    TemptationNotAboveReward,
+    RewardNotAbovePunishment,
+    PunishmentNotAboveSucker,
+    AlternationPays,
+}
+
+impl fmt::Display for PayoffError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            PayoffError::TemptationNotAboveReward => {
+                "the temptation must be greater than the reward, or there is no reason to defect"
+            }
+            PayoffError::RewardNotAbovePunishment => {
+                "the reward must be greater than the punishment, or there is no reason to cooperate"
+            }
+            PayoffError::PunishmentNotAboveSucker => {
+                "the punishment must be greater than the sucker's payout, or defecting is not safer"
+            }
+            PayoffError::AlternationPays => {
+                "twice the reward must be greater than the temptation plus the sucker's payout, \
+                 or taking turns exploiting each other pays better than cooperating"
+            }
+        };
+        write!(f, "not a prisoners' dilemma: {message}")
+    }
+}
+
+impl std::error::Error for PayoffError {}
+
+/// What kind of one-shot game a [`PayoffMatrix`] describes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StageGame {
+    /// A genuine prisoners' dilemma, where `T > R > P > S` and `2R > T + S`. Mutual defection is
+    /// the only equilibrium even though both players would do better by cooperating.
+    PrisonersDilemma,
+    /// The payouts are ordered `T > R > P > S`, so mutual defection is the only equilibrium, but
+    /// `2R <= T + S`. Players who take turns exploiting each other do at least as well as
+    /// players who always cooperate, so repeated games do not reward cooperation as intended.
+    AlternationPays,
+    /// The payouts are not ordered `T > R > P > S`, so there is no dilemma. These are the pure
+    /// strategy equilibria of the game instead, with player 1's move first.
+    NotADilemma { equilibria: Vec<Outcome> },
+}
+
+impl Default for PayoffMatrix {
+    fn default() -> Self {
+        Self {
+            reward: COOPERATE_PAYOUT,
+            temptation: NARC_OUT_OPPONENT_PAYOUT,
+            punishment: DEFECT_PAYOUT,
+            sucker: GOT_NARCED_OUT_PAYOUT,
+        }
+    }
+}
+
+/// Well known payoff matrices, so that experiments can switch between them by name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayoffPreset {
+    /// This crate's own payouts, see [`PayoffMatrix::default`]
+    Default,
+    /// The payouts from Axelrod's tournaments: `T = 5`, `R = 3`, `P = 1`, `S = 0`
+    Axelrod,
+    /// Snowdrift, also known as chicken, where being exploited beats mutual defection. This is
+    /// not a prisoners' dilemma, since the best reply to a defector is to cooperate.
+    Snowdrift,
+    /// This crate's payouts, except that mutual defection costs both players almost as much as
+    /// being exploited
+    HarshPunishment,
+}
+
+impl PayoffPreset {
+    pub const ALL: [PayoffPreset; 4] = [
+        PayoffPreset::Default,
+        PayoffPreset::Axelrod,
+        PayoffPreset::Snowdrift,
+        PayoffPreset::HarshPunishment,
+    ];
+
+    /// The name used for the preset in config files and on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            PayoffPreset::Default => "default",
+            PayoffPreset::Axelrod => "axelrod",
+            PayoffPreset::Snowdrift => "snowdrift",
+            PayoffPreset::HarshPunishment => "harsh-punishment",
+        }
+    }
+
+    pub fn payoffs(self) -> PayoffMatrix {
+        let (reward, temptation, punishment, sucker) = match self {
+            PayoffPreset::Default => return PayoffMatrix::default(),
+            PayoffPreset::Axelrod => (3, 5, 1, 0),
+            PayoffPreset::Snowdrift => (3, 5, 0, 1),
+            PayoffPreset::HarshPunishment => (
+                COOPERATE_PAYOUT,
+                NARC_OUT_OPPONENT_PAYOUT,
+                GOT_NARCED_OUT_PAYOUT + 1,
+                GOT_NARCED_OUT_PAYOUT,
+            ),
+        };
+        PayoffMatrix {
+            reward,
+            temptation,
+            punishment,
+            sucker,
+        }
+    }
+}
+
+/// A name that is not one of the [`PayoffPreset`]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownPreset(pub String);
+
+impl fmt::Display for UnknownPreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = PayoffPreset::ALL.map(PayoffPreset::name);
+        write!(
+            f,
+            "unknown payoff preset `{}`, the available presets are: {}",
+            self.0,
+            names.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for UnknownPreset {}
+
+/// Look up a preset by its [`name`](PayoffPreset::name), ignoring case.
+impl FromStr for PayoffPreset {
+    type Err = UnknownPreset;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        PayoffPreset::ALL
+            .into_iter()
+            .find(|preset| preset.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| UnknownPreset(name.to_string()))
+    }
+}
+
+impl PayoffMatrix {
+    /// The payoffs of the [`PayoffPreset`] with this name, such as `"axelrod"`.
+    pub fn preset(name: &str) -> Result<Self, UnknownPreset> {
+        Ok(name.parse::<PayoffPreset>()?.payoffs())
+    }
+}
+
+/// Separate payoffs for each player, for experiments in which the two players are rewarded
+/// differently. Each player is paid from its own matrix according to its own move and its
+/// opponent's. Most games use the same matrix for both, see [`AsymmetricPayoffs::symmetric`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AsymmetricPayoffs {
+    pub p1: PayoffMatrix,
+    pub p2: PayoffMatrix,
+}
+
+impl AsymmetricPayoffs {
+    pub fn new(p1: PayoffMatrix, p2: PayoffMatrix) -> Self {
+        Self { p1, p2 }
+    }
+
+    /// Payoffs in which both players are paid from the same matrix.
+    pub fn symmetric(payoffs: PayoffMatrix) -> Self {
+        Self::new(payoffs, payoffs)
+    }
+
+    /// The payouts that player 1 and player 2 receive when they play the given moves.
+    pub fn payouts(
+        &self,
+        p1_move: &CooperateOrDefect,
+        p2_move: &CooperateOrDefect,
+    ) -> (isize, isize) {
+        self.outcome_payouts(outcome(*p1_move, *p2_move))
+    }
+
+    /// The payouts that player 1 and player 2 receive for a round with the given outcome.
+    pub fn outcome_payouts(&self, outcome: Outcome) -> (isize, isize) {
+        (
+            self.p1.outcome_payouts(outcome).0,
+            self.p2.outcome_payouts(outcome).1,
+        )
+    }
+}
+
+impl From<PayoffMatrix> for AsymmetricPayoffs {
+    fn from(payoffs: PayoffMatrix) -> Self {
+        Self::symmetric(payoffs)
+    }
+}
@@ -0,0 +1,277 @@
+//! Saved copies of finished games, so that they can be shared and analysed later.
+
+use std::fmt;
+
+use crate::{
+    score, AsymmetricPayoffs, CooperateOrDefect, CooperateOrDefect::*, DynGame, DynStrategy,
+    FromJson, HistoryView, Json, JsonError, PayoffMatrix, RepeatedPrisonersDilemma, StrategyError,
+    ToJson,
+};
+
+/// Everything needed to replay a game: who played and what each of them did.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameRecord {
+    pub p1_name: String,
+    pub p2_name: String,
+    pub p1_moves: Vec<CooperateOrDefect>,
+    pub p2_moves: Vec<CooperateOrDefect>,
+}
+
+/// Every game played in a tournament, together with the settings they were played under so that
+/// the record can be understood on its own.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TournamentRecord {
+    pub rounds: usize,
+    pub payoffs: PayoffMatrix,
+    pub games: Vec<GameRecord>,
+}
+
+impl<P1, P2> RepeatedPrisonersDilemma<P1, P2> {
+    /// Save the players' names and histories from this game.
+    pub fn into_record(self) -> GameRecord {
+        GameRecord {
+            p1_name: self.player_1.name().to_string(),
+            p2_name: self.player_2.name().to_string(),
+            p1_moves: self.player_1_moves,
+            p2_moves: self.player_2_moves,
+        }
+    }
+}
+
+impl DynGame {
+    /// Rebuild a finished game from histories recorded elsewhere, so that it can be scored,
+    /// analysed, or exported like a game played here. The players are named "Player 1" and
+    /// "Player 2" and the default payoffs are used.
+    ///
+    /// The players can only repeat the recorded moves, so in any further rounds both of them
+    /// forfeit, see [`DynStrategy::try_next_move`]:
+    ///
+    /// ```
+    /// # use prisoners_dilemma_competition::{CooperateOrDefect::*, DynGame};
+    /// let mut game = DynGame::from_moves(vec![Cooperate, Defect], vec![Cooperate, Cooperate])?;
+    /// assert_eq!(game.calculate_score(), (30, 5));
+    ///
+    /// game.play_n_rounds(1);
+    /// assert_eq!(game.player_1_moves(), [Cooperate, Defect, Defect]);
+    /// assert_eq!(game.failures().len(), 2);
+    /// # Ok::<(), prisoners_dilemma_competition::LengthMismatch>(())
+    /// ```
+    pub fn from_moves(
+        p1_moves: Vec<CooperateOrDefect>,
+        p2_moves: Vec<CooperateOrDefect>,
+    ) -> Result<Self, LengthMismatch> {
+        Self::replay("Player 1", p1_moves, "Player 2", p2_moves)
+    }
+
+    fn replay(
+        p1_name: &str,
+        p1_moves: Vec<CooperateOrDefect>,
+        p2_name: &str,
+        p2_moves: Vec<CooperateOrDefect>,
+    ) -> Result<Self, LengthMismatch> {
+        if p1_moves.len() != p2_moves.len() {
+            return Err(LengthMismatch {
+                p1_moves: p1_moves.len(),
+                p2_moves: p2_moves.len(),
+            });
+        }
+
+        let mut game = Self::from_players(
+            Box::new(Replayed(p1_name.to_string())),
+            Box::new(Replayed(p2_name.to_string())),
+            AsymmetricPayoffs::default(),
+        );
+        // With no perception noise, each player saw exactly what the other played
+        game.player_1_view = p2_moves.clone();
+        game.player_2_view = p1_moves.clone();
+        (game.player_1_score, game.player_2_score) = score(&game.payoffs, &p1_moves, &p2_moves);
+        let totals = HistoryView::new(&p1_moves, &p2_moves);
+        (game.player_1_defections, game.player_2_defections) =
+            (totals.my_defections, totals.their_defections);
+        (game.player_1_view_defections, game.player_2_view_defections) =
+            (totals.their_defections, totals.my_defections);
+        game.player_1_moves = p1_moves;
+        game.player_2_moves = p2_moves;
+        Ok(game)
+    }
+}
+
+impl GameRecord {
+    /// Rebuild the recorded game, keeping the players' names. See [`DynGame::from_moves`].
+    pub fn into_game(self) -> Result<DynGame, LengthMismatch> {
+        DynGame::replay(&self.p1_name, self.p1_moves, &self.p2_name, self.p2_moves)
+    }
+}
+
+/// Two recorded histories that cannot belong to the same game because they have different
+/// lengths.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LengthMismatch {
+    pub p1_moves: usize,
+    pub p2_moves: usize,
+}
+
+impl fmt::Display for LengthMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "player 1 made {} moves but player 2 made {}",
+            self.p1_moves, self.p2_moves
+        )
+    }
+}
+
+impl std::error::Error for LengthMismatch {}
+
+/// Stands in for a player in a rebuilt game. Its moves are already in the history, and it fails
+/// to choose any more.
+struct Replayed(String);
+
+impl DynStrategy for Replayed {
+    fn name(&self) -> &str {
+        &self.0
+    }
+
+    fn next_move(
+        &mut self,
+        _my_moves: &[CooperateOrDefect],
+        _their_moves: &[CooperateOrDefect],
+    ) -> CooperateOrDefect {
+        Defect
+    }
+
+    fn try_next_move(
+        &mut self,
+        _history: &HistoryView<'_>,
+    ) -> Result<CooperateOrDefect, StrategyError> {
+        Err(StrategyError(format!(
+            "{} was rebuilt from a recording and cannot play more rounds",
+            self.0
+        )))
+    }
+}
+
+impl ToJson for CooperateOrDefect {
+    fn to_json(&self) -> Json {
+        Json::String(format!("{:?}", self))
+    }
+}
+
+impl FromJson for CooperateOrDefect {
+    fn from_json(json: &Json) -> Result<Self, JsonError> {
+        match json.as_str() {
+            Some("Cooperate") => Ok(Cooperate),
+            Some("Defect") => Ok(Defect),
+            _ => Err(JsonError(format!(
+                "expected \"Cooperate\" or \"Defect\", found {json}"
+            ))),
+        }
+    }
+}
+
+impl ToJson for GameRecord {
+    fn to_json(&self) -> Json {
+        Json::Object(vec![
+            ("p1_name".into(), Json::String(self.p1_name.clone())),
+            ("p2_name".into(), Json::String(self.p2_name.clone())),
+            ("p1_moves".into(), moves_to_json(&self.p1_moves)),
+            ("p2_moves".into(), moves_to_json(&self.p2_moves)),
+        ])
+    }
+}
+
+impl FromJson for GameRecord {
+    fn from_json(json: &Json) -> Result<Self, JsonError> {
+        Ok(Self {
+            p1_name: string_from_json(json.field("p1_name")?)?,
+            p2_name: string_from_json(json.field("p2_name")?)?,
+            p1_moves: moves_from_json(json.field("p1_moves")?)?,
+            p2_moves: moves_from_json(json.field("p2_moves")?)?,
+        })
+    }
+}
+
+impl ToJson for PayoffMatrix {
+    fn to_json(&self) -> Json {
+        Json::Object(vec![
+            ("reward".into(), Json::Integer(self.reward as i128)),
+            ("temptation".into(), Json::Integer(self.temptation as i128)),
+            ("punishment".into(), Json::Integer(self.punishment as i128)),
+            ("sucker".into(), Json::Integer(self.sucker as i128)),
+        ])
+    }
+}
+
+impl FromJson for PayoffMatrix {
+    fn from_json(json: &Json) -> Result<Self, JsonError> {
+        Ok(Self {
+            reward: integer_from_json(json.field("reward")?)?,
+            temptation: integer_from_json(json.field("temptation")?)?,
+            punishment: integer_from_json(json.field("punishment")?)?,
+            sucker: integer_from_json(json.field("sucker")?)?,
+        })
+    }
+}
+
+impl ToJson for TournamentRecord {
+    fn to_json(&self) -> Json {
+        Json::Object(vec![
+            ("rounds".into(), Json::Integer(self.rounds as i128)),
+            ("payoffs".into(), self.payoffs.to_json()),
+            (
+                "games".into(),
+                Json::Array(self.games.iter().map(ToJson::to_json).collect()),
+            ),
+        ])
+    }
+}
+
+impl FromJson for TournamentRecord {
+    /// Read back a record written with [`to_json`](ToJson::to_json), which gives the same record:
+    ///
+    /// ```
+    /// # use prisoners_dilemma_competition::*;
+    /// let record = record_tournament(&default_entrants()[..4], true);
+    /// let text = record.to_json().to_string();
+    /// let read = TournamentRecord::from_json(&Json::parse(&text)?)?;
+    /// assert_eq!(read, record);
+    /// # Ok::<(), JsonError>(())
+    /// ```
+    fn from_json(json: &Json) -> Result<Self, JsonError> {
+        Ok(Self {
+            rounds: integer_from_json(json.field("rounds")?)?,
+            payoffs: PayoffMatrix::from_json(json.field("payoffs")?)?,
+            games: json
+                .field("games")?
+                .as_array()
+                .ok_or_else(|| JsonError("expected a list of games".into()))?
+                .iter()
+                .map(GameRecord::from_json)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+pub(crate) fn integer_from_json<T: TryFrom<i128>>(json: &Json) -> Result<T, JsonError> {
+    json.as_integer()
+        .and_then(|n| T::try_from(n).ok())
+        .ok_or_else(|| JsonError(format!("expected an integer in range, found {json}")))
+}
+
+pub(crate) fn string_from_json(json: &Json) -> Result<String, JsonError> {
+    json.as_str()
+        .map(String::from)
+        .ok_or_else(|| JsonError(format!("expected a string, found {json}")))
+}
+
+fn moves_to_json(moves: &[CooperateOrDefect]) -> Json {
+    Json::Array(moves.iter().map(ToJson::to_json).collect())
+}
+
+fn moves_from_json(json: &Json) -> Result<Vec<CooperateOrDefect>, JsonError> {
+    json.as_array()
+        .ok_or_else(|| JsonError(format!("expected a list of moves, found {json}")))?
+        .iter()
+        .map(CooperateOrDefect::from_json)
+        .collect()
+}
@@ -0,0 +1,232 @@
+//! Built-in strategies looked up by name, for choosing strategies at runtime.
+
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use crate::*;
+
+/// A function that creates a fresh player, ready to start a new game.
+pub type NewPlayer = fn() -> Box<dyn DynStrategy>;
+
+/// A function that creates a fresh player from the seed for its random choices.
+type NewSeededPlayer = fn(u64) -> Box<dyn DynStrategy>;
+
+/// Declare the built-in strategies in the order they are listed to users: first the
+/// [`Strategy`] types as `Type => "name"` pairs, and then the strategies with parameters as
+/// `"name" => constructor` pairs, where the constructor takes a seed and creates a player with
+/// the strategy's default parameters. This generates the tables behind [`registry`] and
+/// [`available_strategies`], which the command line and the default tournament roster are
+/// built from, along with [`roster`].
+///
+/// Each name must be the strategy's [`Strategy::NAME`], which is checked at compile time, or
+/// the name its players report.
+macro_rules! register_strategies {
+    (
+        $($strategy:ty => $name:literal),* $(,)?;
+        $($seeded_name:literal => $new_player:expr),* $(,)?
+    ) => {
+        $(
+            const _: () = assert!(
+                same_name(<$strategy as Strategy>::NAME, $name),
+                concat!("the registered name ", stringify!($name), " is not the strategy's NAME"),
+            );
+        )*
+
+        /// Every built-in strategy along with the function that creates a fresh player for it.
+        /// Strategies that make random choices are given the seed 0.
+        const STRATEGIES: &[(&str, NewPlayer)] = &[
+            $(($name, StrategyInstance::<$strategy>::boxed),)*
+            $(($seeded_name, || ($new_player)(0)),)*
+        ];
+
+        /// The same strategies as [`STRATEGIES`], with functions that take the seed.
+        const SEEDED_STRATEGIES: &[(&str, NewSeededPlayer)] = &[
+            $(($name, |_| StrategyInstance::<$strategy>::boxed()),)*
+            $(($seeded_name, $new_player),)*
+        ];
+    };
+}
+
+register_strategies! {
+    AlwaysCooperate => "Always Cooperate",
+    AlwaysDefect => "Always Defect",
+    TitForTat => "Tit for Tat",
+    GrimTrigger => "Grim Trigger",
+    Pavlov => "Pavlov",
+    TitForTwoTats => "Tit for Two Tats",
+    TwoTitsForTat => "Two Tits for Tat",
+    SuspiciousTitForTat => "Suspicious Tit for Tat",
+    Gradual => "Gradual",
+    Prober => "Prober",
+    SoftMajority => "Soft Majority",
+    HardMajority => "Hard Majority",
+    Adaptive => "Adaptive",
+    ContriteTitForTat => "Contrite Tit for Tat",
+    FirmButFair => "Firm but Fair",
+    ReverseTitForTat => "Reverse Tit for Tat",
+    Alternator => "Alternator",
+    Handshake => "Handshake",
+    OmegaTitForTat => "Omega Tit for Tat",
+    Tester => "Tester",
+    Detective => "Detective",
+    GradualKiller => "Gradual Killer";
+
+    // Ensemble, Markov, and Field Tit for Tat are not here: they are built from other players, a
+    // table of probabilities, and a memory shared across games, none of which has a default
+    "Random" => |seed| valid(Random::new(0.5, seed)),
+    "Generous Tit for Tat" => |seed| valid(GenerousTitForTat::new(1.0 / 3.0, seed)),
+    "Tunable Tit for Tat" => |seed| valid(TunableTitForTat::new(2, 1.0 / 3.0, seed)),
+    "Joss" => |seed| valid(Joss::new(0.1, seed)),
+    "Remorseful Prober" => |seed| valid(RemorsefulProber::new(0.1, seed)),
+    "Stochastic Tit for Tat" => |seed| valid(StochasticTitForTat::new(0.5, seed)),
+    "Forgiving Grudger" => |_| Box::new(ForgivingGrudger::new(3)),
+    "N-Memory Tit for Tat" => |_| Box::new(NMemoryTitForTat::new(3)),
+    "Delayed Tit for Tat" => |_| Box::new(DelayedTitForTat::new(2)),
+    "Aspiration" => |_| {
+        let payoffs = PayoffMatrix::default();
+        Box::new(Aspiration::new(payoffs.punishment, payoffs))
+    },
+    "Catch Up" => |seed| {
+        let payoffs = PayoffMatrix::default();
+        valid(CatchUp::new(payoffs.temptation - payoffs.sucker, 0.5, payoffs, seed))
+    },
+    "Model-Based Best Responder" => |_| {
+        Box::new(ModelBasedBestResponder::new(PayoffMatrix::default()))
+    },
+    "Extortionate" => |seed| {
+        Box::new(MemoryOne::extortionate(2.0, &PayoffMatrix::default(), seed))
+    },
+}
+
+/// Other names for built-in strategies, each with the name the strategy is registered under.
+/// These strategies make exactly the same moves as the ones they stand for, so they are not
+/// listed or entered in tournaments separately, but they can still be looked up by name.
+const ALIASES: &[(&str, &str)] = &[
+    ("Simpleton", "Pavlov"),
+    ("Mimic", "Soft Majority"),
+    ("Copykitten", "Tit for Two Tats"),
+];
+
+/// A player created with default parameters, which are always valid.
+fn valid<S: DynStrategy + 'static>(player: Result<S, InvalidProbability>) -> Box<dyn DynStrategy> {
+    Box::new(player.expect("the default parameters are valid"))
+}
+
+/// Whether two strings are equal, in a form that can be used in constants.
+const fn same_name(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// A map from each built-in strategy's name to a function that creates a fresh player for it.
+/// Strategies with parameters are created with their default parameters, and those that make
+/// random choices with the seed 0.
+///
+/// Every registered strategy can be created, and its players report the name it is registered
+/// under:
+///
+/// ```
+/// # use prisoners_dilemma_competition::*;
+/// let registry = registry();
+/// for name in available_strategies() {
+///     assert_eq!(registry[name]().name(), name);
+/// }
+/// assert!(registry.contains_key(TitForTat::NAME));
+/// assert!(registry.contains_key("Generous Tit for Tat"));
+/// ```
+pub fn registry() -> HashMap<&'static str, NewPlayer> {
+    STRATEGIES.iter().copied().collect()
+}
+
+/// The names of all the built-in strategies.
+pub fn available_strategies() -> Vec<&'static str> {
+    STRATEGIES.iter().map(|&(name, _)| name).collect()
+}
+
+/// An entrant for every built-in strategy, in the order they are listed to users, for the
+/// default tournament. Strategies that make random choices get a different seed for each game,
+/// see [`Entrant::seeded`].
+pub fn default_entrants() -> Vec<Entrant> {
+    SEEDED_STRATEGIES
+        .iter()
+        .map(|&(name, new_player)| Entrant::seeded(name, new_player))
+        .collect()
+}
+
+/// A fresh player for every built-in strategy, in the order they are listed to users.
+pub fn roster() -> Vec<Box<dyn DynStrategy>> {
+    STRATEGIES
+        .iter()
+        .map(|&(_, new_player)| new_player())
+        .collect()
+}
+
+/// Create a fresh player for the built-in strategy with the given name, or with one of its
+/// other names such as `"Simpleton"` for [`Pavlov`].
+pub fn create_strategy(name: &str) -> Result<Box<dyn DynStrategy>, UnknownStrategy> {
+    let registered = ALIASES
+        .iter()
+        .find(|&&(alias, _)| alias == name)
+        .map_or(name, |&(_, known)| known);
+    STRATEGIES
+        .iter()
+        .find(|&&(known, _)| known == registered)
+        .map(|&(_, new_player)| new_player())
+        .ok_or_else(|| UnknownStrategy(name.to_string()))
+}
+
+/// A strategy name that is not in the registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownStrategy(pub String);
+
+impl fmt::Display for UnknownStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown strategy `{}`, the available strategies are: {}",
+            self.0,
+            available_strategies().join(", ")
+        )
+    }
+}
+
+impl std::error::Error for UnknownStrategy {}
+
+/// Look up a built-in strategy by name, ignoring case and treating hyphens, underscores, and
+/// spaces as the same, so `"tit-for-tat"`, `"TIT_FOR_TAT"`, and `"Tit for Tat"` all work. The
+/// strategies' other names work the same way.
+impl FromStr for Box<dyn DynStrategy> {
+    type Err = UnknownStrategy;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let wanted = normalize(name);
+        let wanted = ALIASES
+            .iter()
+            .find(|&&(alias, _)| normalize(alias) == wanted)
+            .map_or(wanted, |&(_, known)| normalize(known));
+        STRATEGIES
+            .iter()
+            .find(|&&(known, _)| normalize(known) == wanted)
+            .map(|&(_, new_player)| new_player())
+            .ok_or_else(|| UnknownStrategy(name.to_string()))
+    }
+}
+
+fn normalize(name: &str) -> String {
+    name.trim()
+        .chars()
+        .map(|c| match c {
+            '-' | '_' => ' ',
+            c => c.to_ascii_lowercase(),
+        })
+        .collect()
+}
@@ -0,0 +1,358 @@
+//! Human readable summaries of tournament results.
+
+use std::cmp::Reverse;
+
+use crate::{Entrant, GameResult};
+
+/// Render tournament results as a table with each strategy's position, name, and total score.
+///
+/// The results are expected to be sorted from highest to lowest score, as returned by
+/// `run_tournament`. Strategies with equal scores share a position, and the next position skips
+/// ahead accordingly (1, 2, 2, 4).
+pub fn format_leaderboard(results: &[(&str, isize)]) -> String {
+    let rank_width = results.len().to_string().len().max("Rank".len());
+    let name_width = results
+        .iter()
+        .map(|(name, _)| name.len())
+        .chain(["Strategy".len()])
+        .max()
+        .unwrap_or_default();
+    let score_width = results
+        .iter()
+        .map(|(_, score)| score.to_string().len())
+        .chain(["Score".len()])
+        .max()
+        .unwrap_or_default();
+
+    let mut table = format!(
+        "{:>rank_width$}  {:<name_width$}  {:>score_width$}\n",
+        "Rank", "Strategy", "Score"
+    );
+
+    let mut rank = 0;
+    for (i, (name, score)) in results.iter().enumerate() {
+        if i == 0 || results[i - 1].1 != *score {
+            rank = i + 1;
+        }
+        table += &format!("{rank:>rank_width$}  {name:<name_width$}  {score:>score_width$}\n");
+    }
+
+    table
+}
+
+/// The same as [`format_leaderboard`], but with a `*` after the names of the entrants that make
+/// random choices, and a note under the table explaining it, so that readers can tell which
+/// scores would change with a different seed.
+pub fn format_annotated_leaderboard(results: &[(&str, isize)], entrants: &[Entrant]) -> String {
+    let random = entrants
+        .iter()
+        .filter(|entrant| !entrant.is_deterministic())
+        .map(Entrant::name)
+        .collect::<Vec<_>>();
+    let names = results
+        .iter()
+        .map(|&(name, _)| {
+            if random.contains(&name) {
+                format!("{name} *")
+            } else {
+                name.to_string()
+            }
+        })
+        .collect::<Vec<_>>();
+    let marked = names
+        .iter()
+        .zip(results)
+        .map(|(name, &(_, score))| (name.as_str(), score))
+        .collect::<Vec<_>>();
+
+    let mut table = format_leaderboard(&marked);
+    if !random.is_empty() {
+        table += "\n* makes random choices, so its score depends on the seed\n";
+    }
+    table
+}
+
+/// Print tournament results as a ranked table, see [`format_leaderboard`].
+pub fn print_leaderboard(results: &[(&str, isize)]) {
+    print!("{}", format_leaderboard(results));
+}
+
+/// Render tournament results as a GitHub-flavored Markdown table, for pasting into issues and
+/// documentation. Positions are given as in [`format_leaderboard`], and any `|` in a strategy's
+/// name is escaped so that it does not split the row.
+pub fn to_markdown(results: &[(&str, isize)]) -> String {
+    let mut table = String::from("| Rank | Strategy | Score |\n| ---: | :--- | ---: |\n");
+
+    let mut rank = 0;
+    for (i, (name, score)) in results.iter().enumerate() {
+        if i == 0 || results[i - 1].1 != *score {
+            rank = i + 1;
+        }
+        table += &format!("| {rank} | {} | {score} |\n", name.replace('|', "\\|"));
+    }
+
+    table
+}
+
+/// Group tournament results into ranks, using standard competition ranking: strategies with
+/// equal scores share a rank, and the next rank skips ahead by the size of the group (1, 2, 2,
+/// 4). The results do not need to be sorted. Within a rank, strategies keep their order in
+/// `results`.
+pub fn ranking<'a>(results: &[(&'a str, isize)]) -> Vec<(usize, Vec<&'a str>)> {
+    let mut sorted = results.to_vec();
+    sorted.sort_by_key(|&(_, score)| Reverse(score));
+
+    let mut ranks: Vec<(usize, Vec<&str>)> = Vec::new();
+    for (i, (name, score)) in sorted.iter().enumerate() {
+        if i > 0 && sorted[i - 1].1 == *score {
+            ranks.last_mut().unwrap().1.push(name);
+        } else {
+            ranks.push((i + 1, vec![name]));
+        }
+    }
+
+    ranks
+}
+
+/// How one strategy's standing changed between two sets of tournament results, see
+/// [`diff_results`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RankChange<'a> {
+    pub name: &'a str,
+    /// The strategy's rank and score in the first results, or `None` if it was not in them
+    pub before: Option<(usize, isize)>,
+    /// The strategy's rank and score in the second results, or `None` if it is not in them
+    pub after: Option<(usize, isize)>,
+}
+
+/// Which way a strategy moved in the rankings, see [`RankChange::movement`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Movement {
+    /// Up by this many places
+    Up(usize),
+    /// Down by this many places
+    Down(usize),
+    Same,
+    /// Only in the second results
+    Added,
+    /// Only in the first results
+    Removed,
+}
+
+impl RankChange<'_> {
+    /// How much the strategy's score went up, or `None` if it is missing from either results.
+    pub fn score_delta(&self) -> Option<isize> {
+        let ((_, before), (_, after)) = (self.before?, self.after?);
+        Some(after.saturating_sub(before))
+    }
+
+    pub fn movement(&self) -> Movement {
+        match (self.before, self.after) {
+            (Some((before, _)), Some((after, _))) if after < before => Movement::Up(before - after),
+            (Some((before, _)), Some((after, _))) if after > before => {
+                Movement::Down(after - before)
+            }
+            (Some(_), Some(_)) => Movement::Same,
+            (None, _) => Movement::Added,
+            (_, None) => Movement::Removed,
+        }
+    }
+}
+
+/// Compare two sets of tournament results, such as from before and after a change to the code,
+/// and report how each strategy's rank and score changed. Ranks are worked out as in
+/// [`ranking`], so the results do not need to be sorted. The changes are listed in the order of
+/// `after`, followed by the strategies that were removed, in the order of `before`.
+pub fn diff_results<'a>(
+    before: &[(&'a str, isize)],
+    after: &[(&'a str, isize)],
+) -> Vec<RankChange<'a>> {
+    let standings = |results: &[(&'a str, isize)]| {
+        let ranks = ranking(results);
+        results
+            .iter()
+            .map(|&(name, score)| {
+                let (rank, _) = ranks
+                    .iter()
+                    .find(|(_, names)| names.contains(&name))
+                    .unwrap();
+                (name, (*rank, score))
+            })
+            .collect::<Vec<_>>()
+    };
+    let (before, after) = (standings(before), standings(after));
+    let find = |standings: &[(&str, (usize, isize))], name: &str| {
+        standings
+            .iter()
+            .find(|&&(n, _)| n == name)
+            .map(|&(_, standing)| standing)
+    };
+
+    let changed = after.iter().map(|&(name, standing)| RankChange {
+        name,
+        before: find(&before, name),
+        after: Some(standing),
+    });
+    let removed = before
+        .iter()
+        .filter(|&&(name, _)| find(&after, name).is_none())
+        .map(|&(name, standing)| RankChange {
+            name,
+            before: Some(standing),
+            after: None,
+        });
+    changed.chain(removed).collect()
+}
+
+/// Render the results of every game in a tournament as a grid, with one row and one column per
+/// strategy in the order they first appear. Each cell holds the total that the row's strategy
+/// scored against the column's strategy, and the last column holds each row's total. Games a
+/// strategy played against itself appear on the diagonal, counting only player 1's score as in
+/// `run_tournament`. Pairs that never met are shown as `-`.
+pub fn format_score_matrix(games: &[GameResult]) -> String {
+    let (names, cells) = score_cells(games);
+
+    let totals: Vec<isize> = cells
+        .iter()
+        .map(|row| {
+            row.iter()
+                .flatten()
+                .fold(0, |a: isize, &b| a.saturating_add(b))
+        })
+        .collect();
+    let text = |cell: &Option<isize>| cell.map_or_else(|| "-".to_string(), |s| s.to_string());
+
+    let name_width = names
+        .iter()
+        .map(|name| name.len())
+        .max()
+        .unwrap_or_default();
+    let column_widths: Vec<usize> = (0..names.len())
+        .map(|column| {
+            cells
+                .iter()
+                .map(|row| text(&row[column]).len())
+                .chain([names[column].len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+    let total_width = totals
+        .iter()
+        .map(|total| total.to_string().len())
+        .chain(["Total".len()])
+        .max()
+        .unwrap_or_default();
+
+    let mut table = format!("{:name_width$}", "");
+    for (name, width) in names.iter().zip(&column_widths) {
+        table += &format!("  {name:>width$}");
+    }
+    table += &format!("  {:>total_width$}\n", "Total");
+
+    for ((name, row), total) in names.iter().zip(&cells).zip(&totals) {
+        table += &format!("{name:<name_width$}");
+        for (cell, width) in row.iter().zip(&column_widths) {
+            table += &format!("  {:>width$}", text(cell));
+        }
+        table += &format!("  {total:>total_width$}\n");
+    }
+
+    table
+}
+
+/// Print the results of every game in a tournament as a grid, see [`format_score_matrix`].
+pub fn print_score_matrix(games: &[GameResult]) {
+    print!("{}", format_score_matrix(games));
+}
+
+/// Find the strategies that are strictly dominated in a tournament: another strategy scored
+/// more than they did against every opponent that both of them played, not counting their
+/// games against each other or themselves. A dominated strategy could be swapped for its
+/// dominator without doing worse against anyone, so it is not worth entering. Pairs with no
+/// opponents in common are never reported.
+///
+/// Each pair is the dominated strategy followed by the one that dominates it, listed in the
+/// order the strategies first appear in `games`, as in [`format_score_matrix`].
+pub fn dominated_strategies(games: &[GameResult]) -> Vec<(&str, &str)> {
+    let (names, cells) = score_cells(games);
+    let mut dominated = Vec::new();
+    for weaker in 0..names.len() {
+        for stronger in (0..names.len()).filter(|&stronger| stronger != weaker) {
+            let mut common = (0..names.len())
+                .filter(|&opponent| opponent != weaker && opponent != stronger)
+                .filter_map(|opponent| cells[weaker][opponent].zip(cells[stronger][opponent]))
+                .peekable();
+            let has_common = common.peek().is_some();
+            if has_common && common.all(|(weaker, stronger)| weaker < stronger) {
+                dominated.push((names[weaker], names[stronger]));
+            }
+        }
+    }
+    dominated
+}
+
+/// The shades used by [`render_heatmap`], from the lowest scores to the highest
+const SHADES: [char; 4] = ['░', '▒', '▓', '█'];
+
+/// Render the results of every game in a tournament as a grid of shaded blocks, for spotting
+/// patterns at a glance. There is one row per strategy, labelled with its name, and one column
+/// per opponent in the same order, and each cell is shaded by which quarter of all the cells'
+/// scores it falls in, with the highest scores darkest. The scores are totalled as in
+/// [`format_score_matrix`], and pairs that never met are left blank.
+pub fn render_heatmap(games: &[GameResult]) -> String {
+    let (names, cells) = score_cells(games);
+    let mut scores: Vec<isize> = cells.iter().flatten().flatten().copied().collect();
+    scores.sort_unstable();
+
+    let shade = |score: isize| {
+        // The fraction of all the scores that this one is at least as high as
+        let at_or_below = scores.partition_point(|&s| s <= score);
+        let quarter = (at_or_below * SHADES.len()).div_ceil(scores.len());
+        SHADES[quarter - 1]
+    };
+
+    let name_width = names
+        .iter()
+        .map(|name| name.len())
+        .max()
+        .unwrap_or_default();
+    let mut grid = String::new();
+    for (name, row) in names.iter().zip(&cells) {
+        grid += &format!("{name:<name_width$}  ");
+        grid.extend(row.iter().map(|cell| cell.map_or(' ', shade)));
+        grid.push('\n');
+    }
+
+    grid
+}
+
+/// The names of the strategies in the order they first appear in `games`, and the total that
+/// each strategy scored against each other one, indexed in that order. Games a strategy played
+/// against itself only count player 1's score, and pairs that never met are `None`.
+fn score_cells(games: &[GameResult]) -> (Vec<&str>, Vec<Vec<Option<isize>>>) {
+    let mut names: Vec<&str> = Vec::new();
+    for game in games {
+        for name in [&game.p1_name, &game.p2_name] {
+            if !names.contains(&name.as_str()) {
+                names.push(name);
+            }
+        }
+    }
+    let index = |name: &str| names.iter().position(|n| *n == name).unwrap();
+
+    let mut cells: Vec<Vec<Option<isize>>> = vec![vec![None; names.len()]; names.len()];
+    let mut add = |row: usize, column: usize, score: isize| {
+        let cell = &mut cells[row][column];
+        *cell = Some(cell.unwrap_or(0).saturating_add(score));
+    };
+    for game in games {
+        let (p1, p2) = (index(&game.p1_name), index(&game.p2_name));
+        add(p1, p2, game.p1_score);
+        if p1 != p2 {
+            add(p2, p1, game.p2_score);
+        }
+    }
+
+    (names, cells)
+}
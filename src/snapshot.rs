@@ -0,0 +1,303 @@
+//! Tournaments that can be stopped part way through, saved to disk, and picked up again later,
+//! so that very long tournaments survive being interrupted.
+
+use std::{fmt, fs, path::Path};
+
+use crate::{
+    integer_from_json, string_from_json,
+    tournament::{pairings, play_seeded_game, tally, Played},
+    Entrant, FromJson, Json, JsonError, TieBreak, ToJson, TournamentConfig,
+};
+
+/// A round-robin tournament that is played a few games at a time. It plays exactly the same
+/// games, in the same order and with the same seeds, as
+/// [`run_tournament_with_config`](crate::run_tournament_with_config), and gives the same
+/// results once every game has been played.
+///
+/// Here half of a tournament is played, saved as JSON, and picked up again from the saved copy:
+///
+/// ```
+/// # use prisoners_dilemma_competition::*;
+/// let entrants = [
+///     Entrant::new::<TitForTat>(),
+///     Entrant::new::<AlwaysDefect>(),
+///     Entrant::seeded("Random", |seed| Box::new(Random::new(0.5, seed).unwrap())),
+///     Entrant::new::<GrimTrigger>(),
+///     Entrant::new::<Pavlov>(),
+/// ];
+/// let config = TournamentConfig {
+///     rounds: 50,
+///     noise: 0.05,
+///     seed: 7,
+///     ..TournamentConfig::default()
+/// };
+///
+/// let mut first_half = Tournament::new(&entrants, &config);
+/// first_half.play_games(first_half.total_games() / 2);
+/// let saved = first_half.snapshot().to_json().to_string();
+///
+/// let snapshot = TournamentSnapshot::from_json(&Json::parse(&saved)?)?;
+/// let mut resumed = Tournament::resume(&entrants, &snapshot)?;
+/// assert_eq!(resumed.games_played(), 5);
+/// resumed.play_to_end();
+/// assert_eq!(resumed.results(), run_tournament_with_config(&entrants, &config));
+///
+/// // What Field Tit for Tat has learned is not saved, so it cannot be resumed
+/// let entrants = [FieldTitForTat::entrant(), Entrant::new::<AlwaysDefect>()];
+/// let snapshot = Tournament::new(&entrants, &config).snapshot();
+/// assert!(Tournament::resume(&entrants, &snapshot).is_err());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct Tournament<'a> {
+    entrants: &'a [Entrant],
+    config: TournamentConfig,
+    pairings: Vec<(usize, usize)>,
+    /// The games played so far, which are the first of `pairings`
+    games: Vec<Played>,
+}
+
+/// Everything needed to carry on with a [`Tournament`] from where it was stopped: its settings,
+/// the names of its entrants, and the results of the games played so far. Every game's seeds
+/// are worked out from the tournament's seed and the positions of the two entrants, so no other
+/// random state needs to be saved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TournamentSnapshot {
+    pub config: TournamentConfig,
+    pub entrants: Vec<String>,
+    games: Vec<Played>,
+}
+
+/// Why a tournament could not be saved, loaded, or resumed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotError(pub String);
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid tournament snapshot: {}", self.0)
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl<'a> Tournament<'a> {
+    /// A tournament between `entrants` with the settings in `config`, with no games played yet.
+    pub fn new(entrants: &'a [Entrant], config: &TournamentConfig) -> Self {
+        Self {
+            entrants,
+            config: *config,
+            pairings: pairings(entrants.len(), config.self_play, config.double_round_robin),
+            games: Vec::new(),
+        }
+    }
+
+    /// Carry on with the tournament saved in `snapshot`. The entrants must be the same ones, in
+    /// the same order, as when the snapshot was taken.
+    ///
+    /// Entrants whose players share memory between games, see [`Entrant::shares_state`], are
+    /// refused, since that memory is not part of the snapshot and the resumed tournament would
+    /// not play the same games.
+    pub fn resume(
+        entrants: &'a [Entrant],
+        snapshot: &TournamentSnapshot,
+    ) -> Result<Self, SnapshotError> {
+        if let Some(entrant) = entrants.iter().find(|entrant| entrant.shares_state()) {
+            return Err(SnapshotError(format!(
+                "{} remembers its earlier games, which the snapshot does not include",
+                entrant.name()
+            )));
+        }
+
+        let names = entrants.iter().map(Entrant::name).collect::<Vec<_>>();
+        if snapshot.entrants != names {
+            return Err(SnapshotError(format!(
+                "it was taken with the entrants {}, not {}",
+                snapshot.entrants.join(", "),
+                names.join(", ")
+            )));
+        }
+
+        let mut tournament = Self::new(entrants, &snapshot.config);
+        if snapshot.games.len() > tournament.pairings.len() {
+            return Err(SnapshotError(format!(
+                "it has {} games, but the tournament only has {}",
+                snapshot.games.len(),
+                tournament.pairings.len()
+            )));
+        }
+        tournament.games = snapshot.games.clone();
+        Ok(tournament)
+    }
+
+    /// Save the progress so far, to be carried on with [`resume`](Self::resume).
+    pub fn snapshot(&self) -> TournamentSnapshot {
+        TournamentSnapshot {
+            config: self.config,
+            entrants: self
+                .entrants
+                .iter()
+                .map(|entrant| entrant.name().to_string())
+                .collect(),
+            games: self.games.clone(),
+        }
+    }
+
+    pub fn games_played(&self) -> usize {
+        self.games.len()
+    }
+
+    pub fn total_games(&self) -> usize {
+        self.pairings.len()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.games.len() == self.pairings.len()
+    }
+
+    /// Play up to `games` more games, stopping early if the tournament finishes, and return how
+    /// many were played.
+    pub fn play_games(&mut self, games: usize) -> usize {
+        let remaining = &self.pairings[self.games.len()..];
+        let next = remaining
+            .iter()
+            .take(games)
+            .map(|&(i, j)| Played::of(&play_seeded_game(self.entrants, i, j, &self.config)));
+        let before = self.games.len();
+        self.games.extend(next);
+        self.games.len() - before
+    }
+
+    /// Play every game that has not been played yet.
+    pub fn play_to_end(&mut self) {
+        self.play_games(self.pairings.len());
+    }
+
+    /// The standings from the games played so far, ranked as in
+    /// [`run_tournament_with_config`](crate::run_tournament_with_config).
+    pub fn results(&self) -> Vec<(&'static str, isize)> {
+        let played = &self.pairings[..self.games.len()];
+        tally(self.entrants, played, &self.games, self.config.tie_break)
+    }
+}
+
+impl TournamentSnapshot {
+    /// Write the snapshot to a JSON file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SnapshotError> {
+        let path = path.as_ref();
+        fs::write(path, self.to_json().to_string())
+            .map_err(|e| SnapshotError(format!("could not write {}: {e}", path.display())))
+    }
+
+    /// Read a snapshot written by [`save`](Self::save).
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SnapshotError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .map_err(|e| SnapshotError(format!("could not read {}: {e}", path.display())))?;
+        Json::parse(&text)
+            .and_then(|json| Self::from_json(&json))
+            .map_err(|e| SnapshotError(e.to_string()))
+    }
+}
+
+impl ToJson for TournamentSnapshot {
+    fn to_json(&self) -> Json {
+        let TournamentConfig {
+            seed,
+            rounds,
+            noise,
+            self_play,
+            double_round_robin,
+            tie_break,
+        } = self.config;
+        let integers =
+            |numbers: &[i128]| Json::Array(numbers.iter().copied().map(Json::Integer).collect());
+        let games = self.games.iter().map(|game| {
+            integers(&[
+                game.scores.0 as i128,
+                game.scores.1 as i128,
+                game.cooperations.0 as i128,
+                game.cooperations.1 as i128,
+                game.rounds as i128,
+            ])
+        });
+
+        Json::Object(vec![
+            ("seed".into(), Json::Integer(seed as i128)),
+            ("rounds".into(), Json::Integer(rounds as i128)),
+            ("noise".into(), Json::Float(noise)),
+            ("self_play".into(), Json::Bool(self_play)),
+            ("double_round_robin".into(), Json::Bool(double_round_robin)),
+            ("tie_break".into(), Json::String(format!("{tie_break:?}"))),
+            (
+                "entrants".into(),
+                Json::Array(self.entrants.iter().cloned().map(Json::String).collect()),
+            ),
+            ("games".into(), Json::Array(games.collect())),
+        ])
+    }
+}
+
+impl FromJson for TournamentSnapshot {
+    fn from_json(json: &Json) -> Result<Self, JsonError> {
+        let boolean = |key: &str| match json.field(key)? {
+            Json::Bool(value) => Ok(*value),
+            other => Err(JsonError(format!("expected true or false, found {other}"))),
+        };
+        let list = |key: &str| {
+            json.field(key)?
+                .as_array()
+                .ok_or_else(|| JsonError(format!("expected a list of {key}")))
+        };
+        let tie_break = match json.field("tie_break")?.as_str() {
+            Some("EntryOrder") => TieBreak::EntryOrder,
+            Some("CooperationRate") => TieBreak::CooperationRate,
+            Some("HeadToHead") => TieBreak::HeadToHead,
+            Some("Name") => TieBreak::Name,
+            _ => {
+                return Err(JsonError(format!(
+                    "unknown tie break {}",
+                    json.field("tie_break")?
+                )))
+            }
+        };
+        let noise = json.field("noise")?;
+
+        let config = TournamentConfig {
+            seed: integer_from_json(json.field("seed")?)?,
+            rounds: integer_from_json(json.field("rounds")?)?,
+            noise: noise
+                .as_f64()
+                .ok_or_else(|| JsonError(format!("expected a number, found {noise}")))?,
+            self_play: boolean("self_play")?,
+            double_round_robin: boolean("double_round_robin")?,
+            tie_break,
+        };
+        let entrants = list("entrants")?
+            .iter()
+            .map(string_from_json)
+            .collect::<Result<_, _>>()?;
+        let games = list("games")?
+            .iter()
+            .map(|game| match game.as_array() {
+                Some([p1_score, p2_score, p1_cooperations, p2_cooperations, rounds]) => {
+                    Ok(Played {
+                        scores: (integer_from_json(p1_score)?, integer_from_json(p2_score)?),
+                        cooperations: (
+                            integer_from_json(p1_cooperations)?,
+                            integer_from_json(p2_cooperations)?,
+                        ),
+                        rounds: integer_from_json(rounds)?,
+                    })
+                }
+                _ => Err(JsonError(format!(
+                    "expected a game as a list of five integers, found {game}"
+                ))),
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            config,
+            entrants,
+            games,
+        })
+    }
+}
@@ -0,0 +1,48 @@
+//! Summary statistics of a set of scores, shared by the reports so that they all agree on the
+//! definitions. Every function returns `None` for an empty slice.
+
+/// The arithmetic mean.
+pub fn mean(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// The middle value once sorted, or the mean of the two middle values when there is an even
+/// number of them.
+pub fn median(values: &[f64]) -> Option<f64> {
+    percentile(values, 50.0)
+}
+
+/// The sample standard deviation, which divides by one less than the number of values. A single
+/// value says nothing about the spread, so at least two are needed.
+pub fn std_dev(values: &[f64]) -> Option<f64> {
+    if values.len() < 2 {
+        return None;
+    }
+    let mean = mean(values)?;
+    let squares = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>();
+    Some((squares / (values.len() - 1) as f64).sqrt())
+}
+
+/// The value below which `percent` percent of the values fall, interpolating linearly between
+/// the two nearest values once sorted. The 0th percentile is the smallest value and the 100th
+/// is the largest.
+///
+/// # Panics
+///
+/// Panics if `percent` is not between 0 and 100.
+pub fn percentile(values: &[f64], percent: f64) -> Option<f64> {
+    assert!(
+        (0.0..=100.0).contains(&percent),
+        "percentiles must be between 0 and 100, found {percent}"
+    );
+    if values.is_empty() {
+        return None;
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let position = percent / 100.0 * (sorted.len() - 1) as f64;
+    let (below, above) = (position.floor() as usize, position.ceil() as usize);
+    let fraction = position - below as f64;
+    Some(sorted[below] + (sorted[above] - sorted[below]) * fraction)
+}
@@ -0,0 +1,1925 @@
+//! The built-in strategies that players can choose from.
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    outcome, CooperateOrDefect, CooperateOrDefect::*, DynStrategy, Entrant, HistoryView, Outcome,
+    PayoffMatrix, Rng, Strategy,
+};
+
+/// One of the simplest strategies
+pub struct AlwaysCooperate;
+
+impl Strategy for AlwaysCooperate {
+    const NAME: &'static str = "Always Cooperate";
+    const DESCRIPTION: &'static str = "Cooperates on every move, no matter what.";
+    type State = ();
+
+    fn next_move(
+        _my_moves: &[CooperateOrDefect],
+        _their_moves: &[CooperateOrDefect],
+        _state: &mut (),
+    ) -> CooperateOrDefect {
+        Cooperate
+    }
+}
+
+/// One of the simplest strategies
+pub struct AlwaysDefect;
+
+impl Strategy for AlwaysDefect {
+    const NAME: &'static str = "Always Defect";
+    const DESCRIPTION: &'static str = "Defects on every move, no matter what.";
+    type State = ();
+
+    fn next_move(
+        _my_moves: &[CooperateOrDefect],
+        _their_moves: &[CooperateOrDefect],
+        _state: &mut (),
+    ) -> CooperateOrDefect {
+        Defect
+    }
+}
+
+/// Cooperate on the first turn, then copy whatever the opponent did last turn.
+pub struct TitForTat;
+
+impl Strategy for TitForTat {
+    const NAME: &'static str = "Tit for Tat";
+    const DESCRIPTION: &'static str = "Cooperates first, then copies the opponent's last move.";
+    const AUTHOR: &'static str = "Anatol Rapoport";
+    type State = ();
+
+    fn next_move(
+        _my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+        _state: &mut (),
+    ) -> CooperateOrDefect {
+        match their_moves.last() {
+            None | Some(Cooperate) => Cooperate,
+            Some(Defect) => Defect,
+        }
+    }
+}
+
+/// Cooperate until the opponent defects even once, then defect for the rest of the game.
+///
+/// ```
+/// # use prisoners_dilemma_competition::{CooperateOrDefect::{self, *}, *};
+/// const C: CooperateOrDefect = Cooperate;
+/// const D: CooperateOrDefect = Defect;
+/// assert_moves::<GrimTrigger>(&[
+///     // The opponent always cooperates
+///     (&[], &[], Cooperate),
+///     (&[C, C, C, C], &[C, C, C, C], Cooperate),
+///     // The opponent defects once early on and never again
+///     (&[C], &[D], Defect),
+///     (&[C, D, D, D], &[D, C, C, C], Defect),
+///     // The opponent defects on the last move so far
+///     (&[C, C, C], &[C, C, D], Defect),
+/// ]);
+/// ```
+pub struct GrimTrigger;
+
+impl Strategy for GrimTrigger {
+    const NAME: &'static str = "Grim Trigger";
+    const DESCRIPTION: &'static str =
+        "Cooperates until the opponent defects once, then defects forever.";
+    type State = ();
+
+    fn next_move(
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+        state: &mut (),
+    ) -> CooperateOrDefect {
+        Self::next_move_with_history(&HistoryView::new(my_moves, their_moves), state)
+    }
+
+    fn next_move_with_history(history: &HistoryView<'_>, _state: &mut ()) -> CooperateOrDefect {
+        if history.their_defections > 0 {
+            Defect
+        } else {
+            Cooperate
+        }
+    }
+}
+
+/// Win-stay, lose-shift. Repeat the previous move if it went well (both players cooperated, or
+/// this player defected against a cooperator) and switch to the other move if it went badly.
+/// Cooperates on the first move.
+pub struct Pavlov;
+
+impl Strategy for Pavlov {
+    const NAME: &'static str = "Pavlov";
+    const DESCRIPTION: &'static str =
+        "Repeats its last move after a good outcome and switches after a bad one.";
+    type State = ();
+
+    fn next_move(
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+        _state: &mut (),
+    ) -> CooperateOrDefect {
+        match (my_moves.last(), their_moves.last()) {
+            (None, _) | (_, None) => Cooperate,
+            // Wins: stay
+            (Some(Cooperate), Some(Cooperate)) => Cooperate,
+            (Some(Defect), Some(Cooperate)) => Defect,
+            // Losses: shift
+            (Some(Cooperate), Some(Defect)) => Defect,
+            (Some(Defect), Some(Defect)) => Cooperate,
+        }
+    }
+}
+
+/// A strategy was created with a probability outside of `[0, 1]`. The constructors of the
+/// strategies that make random choices check their probabilities rather than letting values
+/// such as 1.5 silently act like 1.
+///
+/// ```
+/// # use prisoners_dilemma_competition::{InvalidProbability, Joss};
+/// assert!(Joss::new(1.0, 0).is_ok());
+/// assert!(Joss::new(0.0, 0).is_ok());
+/// assert_eq!(
+///     Joss::new(1.5, 0).err(),
+///     Some(InvalidProbability { parameter: "sneak", value: 1.5 })
+/// );
+/// assert!(Joss::new(-0.1, 0).is_err());
+/// assert!(Joss::new(f64::NAN, 0).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InvalidProbability {
+    /// The name of the parameter
+    pub parameter: &'static str,
+    pub value: f64,
+}
+
+impl fmt::Display for InvalidProbability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} must be a probability between 0 and 1, found {}",
+            self.parameter, self.value
+        )
+    }
+}
+
+impl Error for InvalidProbability {}
+
+/// Check that `value` is a probability, so that it can be used as the parameter called
+/// `parameter`.
+fn probability(parameter: &'static str, value: f64) -> Result<f64, InvalidProbability> {
+    if (0.0..=1.0).contains(&value) {
+        Ok(value)
+    } else {
+        Err(InvalidProbability { parameter, value })
+    }
+}
+
+/// Ignore the history entirely and cooperate with probability `p_cooperate` each turn.
+///
+/// The moves come from a seeded generator, so two players created with the same seed play
+/// exactly the same game.
+pub struct Random {
+    pub p_cooperate: f64,
+    rng: Rng,
+}
+
+impl Random {
+    pub fn new(p_cooperate: f64, seed: u64) -> Result<Self, InvalidProbability> {
+        Ok(Self {
+            p_cooperate: probability("p_cooperate", p_cooperate)?,
+            rng: Rng::new(seed),
+        })
+    }
+}
+
+impl DynStrategy for Random {
+    fn name(&self) -> &str {
+        "Random"
+    }
+
+    fn description(&self) -> &str {
+        "Cooperates with a fixed probability, ignoring the opponent."
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+
+    fn next_move(
+        &mut self,
+        _my_moves: &[CooperateOrDefect],
+        _their_moves: &[CooperateOrDefect],
+    ) -> CooperateOrDefect {
+        if self.rng.chance(self.p_cooperate) {
+            Cooperate
+        } else {
+            Defect
+        }
+    }
+}
+
+/// Tit for Tat, except that when the opponent defected it forgives them and cooperates anyway
+/// with probability `forgiveness`. This keeps two reactive players from getting stuck in a
+/// cycle of retaliation after an accidental defection.
+pub struct GenerousTitForTat {
+    pub forgiveness: f64,
+    rng: Rng,
+}
+
+impl GenerousTitForTat {
+    pub fn new(forgiveness: f64, seed: u64) -> Result<Self, InvalidProbability> {
+        Ok(Self {
+            forgiveness: probability("forgiveness", forgiveness)?,
+            rng: Rng::new(seed),
+        })
+    }
+}
+
+impl DynStrategy for GenerousTitForTat {
+    fn name(&self) -> &str {
+        "Generous Tit for Tat"
+    }
+
+    fn description(&self) -> &str {
+        "Tit for Tat that sometimes forgives a defection."
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+
+    fn next_move(
+        &mut self,
+        _my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+    ) -> CooperateOrDefect {
+        match their_moves.last() {
+            None | Some(Cooperate) => Cooperate,
+            Some(Defect) if self.rng.chance(self.forgiveness) => Cooperate,
+            Some(Defect) => Defect,
+        }
+    }
+}
+
+/// A more forgiving Tit for Tat that only defects when the opponent defected in both of the last
+/// two rounds.
+pub struct TitForTwoTats;
+
+impl Strategy for TitForTwoTats {
+    const NAME: &'static str = "Tit for Two Tats";
+    const DESCRIPTION: &'static str = "Defects only after the opponent defects twice in a row.";
+    type State = ();
+
+    fn next_move(
+        _my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+        _state: &mut (),
+    ) -> CooperateOrDefect {
+        match their_moves {
+            [.., Defect, Defect] => Defect,
+            _ => Cooperate,
+        }
+    }
+}
+
+/// Punish every defection by the opponent with two defections. If the opponent defects again
+/// while the punishment is still being handed out, the count starts over at two.
+pub struct TwoTitsForTat;
+
+impl Strategy for TwoTitsForTat {
+    const NAME: &'static str = "Two Tits for Tat";
+    const DESCRIPTION: &'static str =
+        "Defects twice in return for each of the opponent's defections.";
+    /// How many more defections are still owed to the opponent
+    type State = usize;
+
+    fn next_move(
+        _my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+        revenge_owed: &mut usize,
+    ) -> CooperateOrDefect {
+        if let Some(Defect) = their_moves.last() {
+            *revenge_owed = 2;
+        }
+
+        if *revenge_owed > 0 {
+            *revenge_owed -= 1;
+            Defect
+        } else {
+            Cooperate
+        }
+    }
+}
+
+/// A family of reactive strategies with two dials. Each defection by the opponent is punished
+/// with `retaliation_length` defections, starting over if the opponent defects again during the
+/// punishment, except that with probability `forgiveness` the defection is let go and does not
+/// start a punishment. `(1, 0.0)` is Tit for Tat, `(2, 0.0)` is Two Tits for Tat, and `(1, g)` is
+/// Generous Tit for Tat with forgiveness `g`, making the same choices for the same seed.
+///
+/// Sweeping both dials under noise shows how harsh and how forgiving a strategy can be while
+/// still doing well.
+pub struct TunableTitForTat {
+    pub retaliation_length: usize,
+    pub forgiveness: f64,
+    /// How many more defections are still owed to the opponent
+    revenge_owed: usize,
+    rng: Rng,
+}
+
+impl TunableTitForTat {
+    pub fn new(
+        retaliation_length: usize,
+        forgiveness: f64,
+        seed: u64,
+    ) -> Result<Self, InvalidProbability> {
+        Ok(Self {
+            retaliation_length,
+            forgiveness: probability("forgiveness", forgiveness)?,
+            revenge_owed: 0,
+            rng: Rng::new(seed),
+        })
+    }
+}
+
+impl DynStrategy for TunableTitForTat {
+    fn name(&self) -> &str {
+        "Tunable Tit for Tat"
+    }
+
+    fn description(&self) -> &str {
+        "Punishes each defection with a set number of defections, but sometimes forgives."
+    }
+
+    fn is_deterministic(&self) -> bool {
+        self.forgiveness == 0.0 || self.forgiveness == 1.0
+    }
+
+    fn next_move(
+        &mut self,
+        _my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+    ) -> CooperateOrDefect {
+        if their_moves.last() == Some(&Defect) && !self.rng.chance(self.forgiveness) {
+            self.revenge_owed = self.retaliation_length;
+        }
+
+        if self.revenge_owed > 0 {
+            self.revenge_owed -= 1;
+            Defect
+        } else {
+            Cooperate
+        }
+    }
+}
+
+/// Tit for Tat, except that it defects on the first move instead of cooperating.
+pub struct SuspiciousTitForTat;
+
+impl Strategy for SuspiciousTitForTat {
+    const NAME: &'static str = "Suspicious Tit for Tat";
+    const DESCRIPTION: &'static str = "Tit for Tat that opens with a defection.";
+    type State = ();
+
+    fn next_move(
+        _my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+        _state: &mut (),
+    ) -> CooperateOrDefect {
+        match their_moves.last() {
+            None | Some(Defect) => Defect,
+            Some(Cooperate) => Cooperate,
+        }
+    }
+}
+
+/// Cooperate until the opponent defects. After the opponent's nth defection, defect n times in
+/// a row and then cooperate twice to calm things down before going back to cooperating.
+///
+/// Defections made while a punishment or the calming moves are under way are counted towards the
+/// next punishment but do not interrupt the current one.
+///
+/// Against an opponent that defects on the first and fifth moves, the first defection is
+/// answered with D then C C, and the second with D D then C C:
+///
+/// ```
+/// # use prisoners_dilemma_competition::{CooperateOrDefect::{self, *}, *};
+/// struct Script(Vec<CooperateOrDefect>);
+///
+/// impl DynStrategy for Script {
+///     fn name(&self) -> &str {
+///         "Script"
+///     }
+///
+///     fn next_move(
+///         &mut self,
+///         my_moves: &[CooperateOrDefect],
+///         _their_moves: &[CooperateOrDefect],
+///     ) -> CooperateOrDefect {
+///         self.0[my_moves.len()]
+///     }
+/// }
+///
+/// const C: CooperateOrDefect = Cooperate;
+/// const D: CooperateOrDefect = Defect;
+/// let script = vec![D, C, C, C, D, C, C, C, C, C];
+/// let mut game = DynGame::new(StrategyInstance::<Gradual>::boxed(), Box::new(Script(script)));
+/// game.play_n_rounds(10);
+/// assert_eq!(game.player_1_moves(), [C, D, C, C, C, D, D, C, C, C]);
+/// ```
+pub struct Gradual;
+
+/// What [`Gradual`] remembers between moves.
+#[derive(Default)]
+pub struct GradualState {
+    /// How many times the opponent has defected so far
+    opponent_defections: usize,
+    /// How many more defections are left in the current punishment
+    punishments_left: usize,
+    /// How many more calming cooperations follow the current punishment
+    calming_left: usize,
+}
+
+impl Strategy for Gradual {
+    const NAME: &'static str = "Gradual";
+    const DESCRIPTION: &'static str =
+        "Punishes each defection with one more defection than the last, then makes peace.";
+    const AUTHOR: &'static str = "Bruno Beaufils, Jean-Paul Delahaye and Philippe Mathieu";
+    type State = GradualState;
+
+    fn next_move(
+        _my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+        state: &mut GradualState,
+    ) -> CooperateOrDefect {
+        let provoked = matches!(their_moves.last(), Some(Defect));
+        if provoked {
+            state.opponent_defections += 1;
+        }
+
+        if state.punishments_left > 0 {
+            state.punishments_left -= 1;
+            if state.punishments_left == 0 {
+                state.calming_left = 2;
+            }
+            Defect
+        } else if state.calming_left > 0 {
+            state.calming_left -= 1;
+            Cooperate
+        } else if provoked {
+            // This move is the first defection of the punishment
+            state.punishments_left = state.opponent_defections - 1;
+            if state.punishments_left == 0 {
+                state.calming_left = 2;
+            }
+            Defect
+        } else {
+            Cooperate
+        }
+    }
+}
+
+/// Open with Defect, Cooperate, Cooperate to test the opponent. If the opponent cooperated on
+/// the second and third moves it is a pushover, so defect for the rest of the game. Otherwise
+/// play Tit for Tat.
+pub struct Prober;
+
+impl Strategy for Prober {
+    const NAME: &'static str = "Prober";
+    const DESCRIPTION: &'static str =
+        "Opens with D, C, C and exploits opponents that never retaliate.";
+    type State = ();
+
+    fn next_move(
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+        _state: &mut (),
+    ) -> CooperateOrDefect {
+        match (my_moves.len(), their_moves) {
+            (0, _) => Defect,
+            (1 | 2, _) => Cooperate,
+            (_, [_, Cooperate, Cooperate, ..]) => Defect,
+            (_, [.., Defect]) => Defect,
+            _ => Cooperate,
+        }
+    }
+}
+
+/// A sneaky Tit for Tat: it copies the opponent's last move, but whenever it would cooperate it
+/// defects instead with probability `sneak`.
+pub struct Joss {
+    pub sneak: f64,
+    rng: Rng,
+}
+
+impl Joss {
+    pub fn new(sneak: f64, seed: u64) -> Result<Self, InvalidProbability> {
+        Ok(Self {
+            sneak: probability("sneak", sneak)?,
+            rng: Rng::new(seed),
+        })
+    }
+}
+
+impl DynStrategy for Joss {
+    fn name(&self) -> &str {
+        "Joss"
+    }
+
+    fn description(&self) -> &str {
+        "Tit for Tat that sometimes sneaks in a defection."
+    }
+
+    fn author(&self) -> &str {
+        "Johann Joss"
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+
+    fn next_move(
+        &mut self,
+        _my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+    ) -> CooperateOrDefect {
+        match their_moves.last() {
+            Some(Defect) => Defect,
+            None | Some(Cooperate) if self.rng.chance(self.sneak) => Defect,
+            None | Some(Cooperate) => Cooperate,
+        }
+    }
+}
+
+/// Tit for Tat that probes the opponent: whenever it would cooperate after the first move, it
+/// defects instead with probability `probe`. Unlike [`Joss`], it remembers its probes. If the
+/// opponent retaliates in the round right after a probe, it knows it caused that defection and
+/// cooperates instead of retaliating in turn, so a probe costs one round of punishment rather
+/// than starting a chain of mutual defection.
+pub struct RemorsefulProber {
+    pub probe: f64,
+    /// The round in which the most recent probe was played
+    last_probe: Option<usize>,
+    rng: Rng,
+}
+
+impl RemorsefulProber {
+    pub fn new(probe: f64, seed: u64) -> Result<Self, InvalidProbability> {
+        Ok(Self {
+            probe: probability("probe", probe)?,
+            last_probe: None,
+            rng: Rng::new(seed),
+        })
+    }
+}
+
+impl DynStrategy for RemorsefulProber {
+    fn name(&self) -> &str {
+        "Remorseful Prober"
+    }
+
+    fn description(&self) -> &str {
+        "Tit for Tat that probes with random defections and forgives the retaliation."
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+
+    fn next_move(
+        &mut self,
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+    ) -> CooperateOrDefect {
+        let round = my_moves.len();
+        let probe_was_punished = round >= 2
+            && self.last_probe == Some(round - 2)
+            && matches!(their_moves.last(), Some(Defect));
+        if probe_was_punished {
+            return Cooperate;
+        }
+
+        match their_moves.last() {
+            None => Cooperate,
+            Some(Defect) => Defect,
+            Some(Cooperate) if self.rng.chance(self.probe) => {
+                self.last_probe = Some(round);
+                Defect
+            }
+            Some(Cooperate) => Cooperate,
+        }
+    }
+}
+
+/// Cooperate on the first move, then copy the opponent's last move with probability `imitate`
+/// and otherwise repeat its own last move. With `imitate` at 1 this is Tit for Tat, and with
+/// `imitate` at 0 it cooperates forever.
+pub struct StochasticTitForTat {
+    pub imitate: f64,
+    rng: Rng,
+}
+
+impl StochasticTitForTat {
+    pub fn new(imitate: f64, seed: u64) -> Result<Self, InvalidProbability> {
+        Ok(Self {
+            imitate: probability("imitate", imitate)?,
+            rng: Rng::new(seed),
+        })
+    }
+}
+
+impl DynStrategy for StochasticTitForTat {
+    fn name(&self) -> &str {
+        "Stochastic Tit for Tat"
+    }
+
+    fn description(&self) -> &str {
+        "Copies the opponent's last move some of the time and repeats its own otherwise."
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+
+    fn next_move(
+        &mut self,
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+    ) -> CooperateOrDefect {
+        let (Some(my_last), Some(their_last)) = (my_moves.last(), their_moves.last()) else {
+            return Cooperate;
+        };
+        let copied = if self.rng.chance(self.imitate) {
+            their_last
+        } else {
+            my_last
+        };
+        *copied
+    }
+}
+
+/// A Grim Trigger that eventually forgives. It cooperates until the opponent defects, then
+/// defects for the next `forgive_after` rounds before going back to cooperating. If the opponent
+/// defects again while it is being punished, the punishment starts over.
+pub struct ForgivingGrudger {
+    pub forgive_after: usize,
+    /// How many more rounds of punishment are owed
+    punishment_left: usize,
+}
+
+impl ForgivingGrudger {
+    pub fn new(forgive_after: usize) -> Self {
+        Self {
+            forgive_after,
+            punishment_left: 0,
+        }
+    }
+}
+
+impl DynStrategy for ForgivingGrudger {
+    fn name(&self) -> &str {
+        "Forgiving Grudger"
+    }
+
+    fn description(&self) -> &str {
+        "Punishes each defection with a fixed number of defections, then forgives."
+    }
+
+    fn next_move(
+        &mut self,
+        _my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+    ) -> CooperateOrDefect {
+        if let Some(Defect) = their_moves.last() {
+            self.punishment_left = self.forgive_after;
+        }
+
+        if self.punishment_left > 0 {
+            self.punishment_left -= 1;
+            Defect
+        } else {
+            Cooperate
+        }
+    }
+}
+
+/// Tit for Tat with a longer memory. It cooperates unless the opponent defected in more than
+/// half of its last `window` moves, or of all its moves early in the game. With a `window` of 1
+/// this is exactly Tit for Tat, and longer windows tolerate the occasional defection.
+pub struct NMemoryTitForTat {
+    pub window: usize,
+}
+
+impl NMemoryTitForTat {
+    pub fn new(window: usize) -> Self {
+        Self { window }
+    }
+}
+
+impl DynStrategy for NMemoryTitForTat {
+    fn name(&self) -> &str {
+        "N-Memory Tit for Tat"
+    }
+
+    fn description(&self) -> &str {
+        "Defects if the opponent defected in most of its recent moves."
+    }
+
+    fn next_move(
+        &mut self,
+        _my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+    ) -> CooperateOrDefect {
+        let recent = &their_moves[their_moves.len().saturating_sub(self.window)..];
+        if 2 * count_defections(recent) > recent.len() {
+            Defect
+        } else {
+            Cooperate
+        }
+    }
+}
+
+/// Tit for Tat with a reaction lag: it copies the opponent's move from `delay` rounds ago, and
+/// cooperates until the game is that long. With a `delay` of 1 this is exactly Tit for Tat.
+/// Longer delays make it out of step with reactive opponents, so it can fall into long cycles of
+/// alternating punishments.
+pub struct DelayedTitForTat {
+    pub delay: usize,
+}
+
+impl DelayedTitForTat {
+    pub fn new(delay: usize) -> Self {
+        Self { delay }
+    }
+}
+
+impl DynStrategy for DelayedTitForTat {
+    fn name(&self) -> &str {
+        "Delayed Tit for Tat"
+    }
+
+    fn description(&self) -> &str {
+        "Copies the opponent's move from a fixed number of rounds ago."
+    }
+
+    fn next_move(
+        &mut self,
+        _my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+    ) -> CooperateOrDefect {
+        match their_moves
+            .len()
+            .checked_sub(self.delay)
+            .and_then(|round| their_moves.get(round))
+        {
+            None | Some(Cooperate) => Cooperate,
+            Some(Defect) => Defect,
+        }
+    }
+}
+
+/// A player made up of several others. Every round each member picks a move as if it were
+/// playing alone, and the ensemble plays whichever move has the greater total weight behind it.
+/// A tie is resolved by defecting if `defect_on_tie` is set and by cooperating otherwise.
+pub struct Ensemble {
+    /// The members and their weights
+    members: Vec<(Box<dyn DynStrategy>, f64)>,
+    pub defect_on_tie: bool,
+}
+
+impl Ensemble {
+    pub fn new(members: Vec<(Box<dyn DynStrategy>, f64)>, defect_on_tie: bool) -> Self {
+        Self {
+            members,
+            defect_on_tie,
+        }
+    }
+}
+
+impl DynStrategy for Ensemble {
+    fn name(&self) -> &str {
+        "Ensemble"
+    }
+
+    fn description(&self) -> &str {
+        "Plays the weighted majority vote of several strategies."
+    }
+
+    fn is_deterministic(&self) -> bool {
+        self.members
+            .iter()
+            .all(|(member, _)| member.is_deterministic())
+    }
+
+    fn next_move(
+        &mut self,
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+    ) -> CooperateOrDefect {
+        // Every member votes each round, even once the outcome is clear, so that members with
+        // their own state see the whole game
+        let (mut cooperate, mut defect) = (0.0, 0.0);
+        for (member, weight) in &mut self.members {
+            match member.next_move(my_moves, their_moves) {
+                Cooperate => cooperate += *weight,
+                Defect => defect += *weight,
+            }
+        }
+
+        if defect > cooperate || (defect == cooperate && self.defect_on_tie) {
+            Defect
+        } else {
+            Cooperate
+        }
+    }
+}
+
+/// Win-stay, lose-shift with a chosen idea of winning. It cooperates on the first move, and
+/// after that repeats its last move if that round paid it at least `level`, and switches to the
+/// other move if it paid less. With `level` above the punishment payout and at most the reward
+/// payout this is exactly [`Pavlov`].
+///
+/// `payoffs` must be the ones the game is scored with, since they decide what each round paid.
+pub struct Aspiration {
+    pub level: isize,
+    pub payoffs: PayoffMatrix,
+}
+
+impl Aspiration {
+    pub fn new(level: isize, payoffs: PayoffMatrix) -> Self {
+        Self { level, payoffs }
+    }
+}
+
+impl DynStrategy for Aspiration {
+    fn name(&self) -> &str {
+        "Aspiration"
+    }
+
+    fn description(&self) -> &str {
+        "Repeats its last move if it paid at least a set level, and switches otherwise."
+    }
+
+    fn next_move(
+        &mut self,
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+    ) -> CooperateOrDefect {
+        let (Some(my_last), Some(their_last)) = (my_moves.last(), their_moves.last()) else {
+            return Cooperate;
+        };
+
+        let (payout, _) = self.payoffs.payouts(my_last, their_last);
+        if payout >= self.level {
+            *my_last
+        } else {
+            my_last.opposite()
+        }
+    }
+}
+
+/// Plays to keep up with its opponent. It keeps a running total of both players' scores, and
+/// while it trails by more than `gap` points it defects with probability `aggression`. Whenever
+/// it is ahead, level, or behind by no more than `gap`, it cooperates. With an `aggression` of 1
+/// it always defects while trailing by more than the gap.
+///
+/// `payoffs` must be the ones the game is scored with, since they decide the running scores.
+/// Under noise the scores are worked out from the moves as this player saw them.
+pub struct CatchUp {
+    pub gap: isize,
+    pub aggression: f64,
+    pub payoffs: PayoffMatrix,
+    /// This player's and the opponent's scores over the rounds counted so far
+    scores: (isize, isize),
+    /// How many rounds of the history have been counted
+    counted: usize,
+    rng: Rng,
+}
+
+impl CatchUp {
+    pub fn new(
+        gap: isize,
+        aggression: f64,
+        payoffs: PayoffMatrix,
+        seed: u64,
+    ) -> Result<Self, InvalidProbability> {
+        Ok(Self {
+            gap,
+            aggression: probability("aggression", aggression)?,
+            payoffs,
+            scores: (0, 0),
+            counted: 0,
+            rng: Rng::new(seed),
+        })
+    }
+
+    /// How far this player trails its opponent, counting only the rounds it has seen so far. It
+    /// is negative while this player is ahead.
+    pub fn deficit(&self) -> isize {
+        self.scores.1.saturating_sub(self.scores.0)
+    }
+}
+
+impl DynStrategy for CatchUp {
+    fn name(&self) -> &str {
+        "Catch Up"
+    }
+
+    fn description(&self) -> &str {
+        "Cooperates unless it has fallen too far behind, then defects to catch up."
+    }
+
+    fn is_deterministic(&self) -> bool {
+        self.aggression == 0.0 || self.aggression == 1.0
+    }
+
+    fn next_move(
+        &mut self,
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+    ) -> CooperateOrDefect {
+        for (mine, theirs) in my_moves.iter().zip(their_moves).skip(self.counted) {
+            let (my_payout, their_payout) = self.payoffs.payouts(mine, theirs);
+            self.scores = (
+                self.scores.0.saturating_add(my_payout),
+                self.scores.1.saturating_add(their_payout),
+            );
+        }
+        self.counted = my_moves.len();
+
+        if self.deficit() > self.gap && self.rng.chance(self.aggression) {
+            Defect
+        } else {
+            Cooperate
+        }
+    }
+}
+
+/// A learner that models its opponent as a memory-one strategy and exploits the model. For each
+/// outcome of a round it counts how often the opponent went on to cooperate, starting from one
+/// imagined cooperation and one imagined defection so that every estimate begins at a half.
+///
+/// Each move is the one with the best expected payout over this round and the next, assuming
+/// that the opponent follows the model and that it will itself play its best move next round.
+/// This looks far enough ahead to notice an opponent that rewards cooperation, but it only
+/// learns how the opponent reacts to outcomes it has actually seen. It opens by defecting, and
+/// against Tit for Tat it settles into a cycle of exploiting and being punished in turn, never
+/// finding out that mutual cooperation would pay more. Against Always Cooperate it learns that
+/// defection goes unpunished and defects every round.
+///
+/// `payoffs` must be the ones the game is scored with, since they decide which move is best.
+pub struct ModelBasedBestResponder {
+    pub payoffs: PayoffMatrix,
+    /// For each outcome, in the order of [`Outcome::ALL`] and seen from this player's point of
+    /// view, how many times the opponent cooperated and defected in the round after it
+    counts: [(usize, usize); 4],
+    /// How many rounds of the history have been counted
+    counted: usize,
+}
+
+impl ModelBasedBestResponder {
+    pub fn new(payoffs: PayoffMatrix) -> Self {
+        Self {
+            payoffs,
+            counts: [(1, 1); 4],
+            counted: 0,
+        }
+    }
+
+    /// The estimated chance that the opponent cooperates in the round after `outcome`.
+    pub fn cooperation_estimate(&self, outcome: Outcome) -> f64 {
+        let (cooperations, defections) = self.counts[Self::index(outcome)];
+        cooperations as f64 / (cooperations + defections) as f64
+    }
+
+    fn index(outcome: Outcome) -> usize {
+        Outcome::ALL.iter().position(|&o| o == outcome).unwrap()
+    }
+
+    /// The expected payout of playing `my_move` against an opponent that cooperates with
+    /// probability `cooperation`.
+    fn expected_payout(&self, my_move: CooperateOrDefect, cooperation: f64) -> f64 {
+        let (cooperated, _) = self.payoffs.payouts(&my_move, &Cooperate);
+        let (defected, _) = self.payoffs.payouts(&my_move, &Defect);
+        cooperation * cooperated as f64 + (1.0 - cooperation) * defected as f64
+    }
+}
+
+impl DynStrategy for ModelBasedBestResponder {
+    fn name(&self) -> &str {
+        "Model-Based Best Responder"
+    }
+
+    fn description(&self) -> &str {
+        "Learns how the opponent reacts to each outcome and plays the best reply to that."
+    }
+
+    fn next_move(
+        &mut self,
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+    ) -> CooperateOrDefect {
+        for round in self.counted.max(1)..their_moves.len() {
+            let previous = Self::index(outcome(my_moves[round - 1], their_moves[round - 1]));
+            match their_moves[round] {
+                Cooperate => self.counts[previous].0 += 1,
+                Defect => self.counts[previous].1 += 1,
+            }
+        }
+        self.counted = their_moves.len();
+
+        let cooperation_now = match (my_moves.last(), their_moves.last()) {
+            (Some(&my_last), Some(&their_last)) => {
+                self.cooperation_estimate(outcome(my_last, their_last))
+            }
+            _ => 0.5,
+        };
+        let best_payout = |cooperation: f64| {
+            [Cooperate, Defect]
+                .map(|my_move| self.expected_payout(my_move, cooperation))
+                .into_iter()
+                .fold(f64::MIN, f64::max)
+        };
+        let value = |my_move: CooperateOrDefect| {
+            let next_round = cooperation_now
+                * best_payout(self.cooperation_estimate(outcome(my_move, Cooperate)))
+                + (1.0 - cooperation_now)
+                    * best_payout(self.cooperation_estimate(outcome(my_move, Defect)));
+            self.expected_payout(my_move, cooperation_now) + next_round
+        };
+
+        if value(Cooperate) >= value(Defect) {
+            Cooperate
+        } else {
+            Defect
+        }
+    }
+}
+
+/// What one strategy has seen of its opponents across all of its games, shared between the
+/// players it creates, see [`FieldTitForTat`].
+#[derive(Debug, Default)]
+pub struct FieldExperience {
+    cooperations: usize,
+    moves: usize,
+}
+
+impl FieldExperience {
+    /// The fraction of the opponents' moves seen so far that were cooperations, or `None` if
+    /// none have been seen.
+    pub fn cooperation_rate(&self) -> Option<f64> {
+        (self.moves > 0).then(|| self.cooperations as f64 / self.moves as f64)
+    }
+}
+
+/// Tit for Tat that also judges the field as a whole. Every player created by the same
+/// [`FieldTitForTat::entrant`] shares one [`FieldExperience`], so it remembers how its earlier
+/// opponents in a tournament behaved. It plays Tit for Tat against the current opponent, but
+/// defects whenever fewer than half of all the opponents' moves it has seen, in this game and
+/// earlier ones, were cooperations. After meeting enough defectors it opens new games by
+/// defecting.
+///
+/// The shared experience means results depend on the order of the games. Sequential
+/// tournaments play them in a fixed order, but parallel ones do not.
+///
+/// ```
+/// # use prisoners_dilemma_competition::{CooperateOrDefect::*, *};
+/// let entrants = [
+///     FieldTitForTat::entrant(),
+///     Entrant::new::<AlwaysDefect>(),
+///     Entrant::new::<AlwaysDefect>(),
+///     Entrant::new::<AlwaysDefect>(),
+/// ];
+/// run_tournament_with_config(&entrants, &TournamentConfig::default());
+///
+/// // Having met only defectors, it opens its next game by defecting, unlike a fresh entrant
+/// assert_eq!(entrants[0].new_player(0).next_move(&[], &[]), Defect);
+/// assert_eq!(FieldTitForTat::entrant().new_player(0).next_move(&[], &[]), Cooperate);
+/// ```
+pub struct FieldTitForTat {
+    experience: Arc<Mutex<FieldExperience>>,
+}
+
+impl FieldTitForTat {
+    pub fn new(experience: Arc<Mutex<FieldExperience>>) -> Self {
+        Self { experience }
+    }
+
+    /// An entrant whose players all share a fresh experience of the field.
+    pub fn entrant() -> Entrant {
+        let experience = Arc::new(Mutex::new(FieldExperience::default()));
+        Entrant::seeded("Field Tit for Tat", move |_| {
+            Box::new(FieldTitForTat::new(Arc::clone(&experience)))
+        })
+        .sharing_state()
+    }
+}
+
+impl DynStrategy for FieldTitForTat {
+    fn name(&self) -> &str {
+        "Field Tit for Tat"
+    }
+
+    fn description(&self) -> &str {
+        "Tit for Tat that defects while its opponents have mostly defected across the tournament."
+    }
+
+    /// The player's moves depend on the games it played before, not just on the history of
+    /// this one, so its games cannot be reused.
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+
+    fn next_move(
+        &mut self,
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+    ) -> CooperateOrDefect {
+        let mut experience = self
+            .experience
+            .lock()
+            .expect("another player sharing the experience panicked");
+        if let Some(their_last) = their_moves.last() {
+            experience.moves += 1;
+            if *their_last == Cooperate {
+                experience.cooperations += 1;
+            }
+        }
+
+        match experience.cooperation_rate() {
+            Some(rate) if rate < 0.5 => Defect,
+            _ => TitForTat::next_move(my_moves, their_moves, &mut ()),
+        }
+    }
+}
+
+/// Cooperate as long as the opponent has cooperated at least as often as it has defected, so
+/// ties (including the first move) are resolved by cooperating.
+pub struct SoftMajority;
+
+impl Strategy for SoftMajority {
+    const NAME: &'static str = "Soft Majority";
+    const DESCRIPTION: &'static str =
+        "Cooperates while the opponent has cooperated at least as often as it defected.";
+    type State = ();
+
+    fn next_move(
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+        state: &mut (),
+    ) -> CooperateOrDefect {
+        Self::next_move_with_history(&HistoryView::new(my_moves, their_moves), state)
+    }
+
+    fn next_move_with_history(history: &HistoryView<'_>, _state: &mut ()) -> CooperateOrDefect {
+        let defections = history.their_defections;
+        let cooperations = history.their_cooperations();
+        if cooperations >= defections {
+            Cooperate
+        } else {
+            Defect
+        }
+    }
+}
+
+/// Defect as long as the opponent has defected at least as often as it has cooperated, so ties
+/// (including the first move) are resolved by defecting.
+pub struct HardMajority;
+
+impl Strategy for HardMajority {
+    const NAME: &'static str = "Hard Majority";
+    const DESCRIPTION: &'static str =
+        "Defects unless the opponent has cooperated more often than it defected.";
+    type State = ();
+
+    fn next_move(
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+        state: &mut (),
+    ) -> CooperateOrDefect {
+        Self::next_move_with_history(&HistoryView::new(my_moves, their_moves), state)
+    }
+
+    fn next_move_with_history(history: &HistoryView<'_>, _state: &mut ()) -> CooperateOrDefect {
+        let defections = history.their_defections;
+        let cooperations = history.their_cooperations();
+        if defections >= cooperations {
+            Defect
+        } else {
+            Cooperate
+        }
+    }
+}
+
+fn count_defections(moves: &[CooperateOrDefect]) -> usize {
+    moves.iter().filter(|m| matches!(m, Defect)).count()
+}
+
+/// A strategy that only remembers the previous round. Each probability is the chance of
+/// cooperating after the given outcome, written from this player's point of view, so `p_cd` is
+/// used after this player cooperated and the opponent defected. `p_opening` is the chance of
+/// cooperating on the first move.
+///
+/// Many strategies are special cases: `(1, 0, 1, 0)` is Tit for Tat, `(1, 0, 0, 1)` is Pavlov,
+/// and `(1, g, 1, g)` is Generous Tit for Tat with forgiveness `g`.
+pub struct MemoryOne {
+    pub p_cc: f64,
+    pub p_cd: f64,
+    pub p_dc: f64,
+    pub p_dd: f64,
+    pub p_opening: f64,
+    name: &'static str,
+    rng: Rng,
+}
+
+impl MemoryOne {
+    pub fn new(
+        p_cc: f64,
+        p_cd: f64,
+        p_dc: f64,
+        p_dd: f64,
+        p_opening: f64,
+        seed: u64,
+    ) -> Result<Self, InvalidProbability> {
+        Ok(Self {
+            p_cc: probability("p_cc", p_cc)?,
+            p_cd: probability("p_cd", p_cd)?,
+            p_dc: probability("p_dc", p_dc)?,
+            p_dd: probability("p_dd", p_dd)?,
+            p_opening: probability("p_opening", p_opening)?,
+            name: "Memory One",
+            rng: Rng::new(seed),
+        })
+    }
+
+    /// A zero-determinant extortion strategy (Press and Dyson, 2012). Against any opponent, its
+    /// long run average score `s` and the opponent's `o` satisfy `s - P = factor * (o - P)`,
+    /// where `P` is the punishment payout, so it gains `factor` times as much over mutual
+    /// defection as its opponent does. An opponent that tries to maximize its own score must
+    /// therefore hand this strategy an even larger share.
+    ///
+    /// `factor` should be at least 1. The payoffs must be the ones the game is scored with,
+    /// because the probabilities are tuned to them.
+    ///
+    /// Against an opponent that always cooperates, which is the best it can do for itself, and
+    /// against one that plays at random, the relation holds in long games:
+    ///
+    /// ```
+    /// # use prisoners_dilemma_competition::*;
+    /// let payoffs = PayoffMatrix::default();
+    /// let punishment = payoffs.punishment as f64;
+    /// let rounds = 200_000;
+    /// for opponent in [
+    ///     StrategyInstance::<AlwaysCooperate>::boxed(),
+    ///     Box::new(Random::new(0.5, 8).unwrap()),
+    /// ] {
+    ///     let extortionate = MemoryOne::extortionate(3.0, &payoffs, 2);
+    ///     let mut game = DynGame::with_payoffs(Box::new(extortionate), opponent, payoffs);
+    ///     game.play_n_rounds(rounds);
+    ///     let (mine, theirs) = game.calculate_score();
+    ///     let mine = mine as f64 / rounds as f64 - punishment;
+    ///     let theirs = theirs as f64 / rounds as f64 - punishment;
+    ///     assert!(theirs > 0.0);
+    ///     assert!((mine - 3.0 * theirs).abs() < 0.05 * mine, "{mine} vs {theirs}");
+    /// }
+    /// ```
+    pub fn extortionate(factor: f64, payoffs: &PayoffMatrix, seed: u64) -> Self {
+        let reward = payoffs.reward as f64;
+        let temptation = payoffs.temptation as f64;
+        let punishment = payoffs.punishment as f64;
+        let sucker = payoffs.sucker as f64;
+
+        // The scale `phi` can be anything that keeps every probability within [0, 1]. Half of
+        // the largest such value stays well clear of the edges.
+        let phi_max = [
+            (factor - 1.0) * (reward - punishment),
+            factor * (temptation - punishment) + (punishment - sucker),
+            (temptation - punishment) + factor * (punishment - sucker),
+        ]
+        .into_iter()
+        .filter(|&limit| limit > 0.0)
+        .map(|limit| 1.0 / limit)
+        .fold(f64::INFINITY, f64::min);
+        let phi = phi_max / 2.0;
+
+        let p_cc = 1.0 - phi * (factor - 1.0) * (reward - punishment);
+        Self {
+            p_cc,
+            p_cd: 1.0 - phi * (factor * (temptation - punishment) + (punishment - sucker)),
+            p_dc: phi * ((temptation - punishment) + factor * (punishment - sucker)),
+            p_dd: 0.0,
+            p_opening: p_cc,
+            name: "Extortionate",
+            rng: Rng::new(seed),
+        }
+    }
+}
+
+impl DynStrategy for MemoryOne {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn description(&self) -> &str {
+        "Cooperates with probabilities that depend on the last round's outcome."
+    }
+
+    /// Only probabilities strictly between 0 and 1 call for a random choice, so a memory-one
+    /// version of a strategy such as Tit for Tat is deterministic.
+    fn is_deterministic(&self) -> bool {
+        [self.p_opening, self.p_cc, self.p_cd, self.p_dc, self.p_dd]
+            .iter()
+            .all(|&p| p == 0.0 || p == 1.0)
+    }
+
+    fn next_move(
+        &mut self,
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+    ) -> CooperateOrDefect {
+        let p_cooperate = match (my_moves.last(), their_moves.last()) {
+            (Some(Cooperate), Some(Cooperate)) => self.p_cc,
+            (Some(Cooperate), Some(Defect)) => self.p_cd,
+            (Some(Defect), Some(Cooperate)) => self.p_dc,
+            (Some(Defect), Some(Defect)) => self.p_dd,
+            _ => self.p_opening,
+        };
+
+        if self.rng.chance(p_cooperate) {
+            Cooperate
+        } else {
+            Defect
+        }
+    }
+}
+
+/// [`MemoryOne`] generalized to remember the last `memory` rounds. The chance of cooperating is
+/// looked up from the outcomes of those rounds, oldest first and seen from this player's point
+/// of view as if it were player 1. Early in the game, before there are `memory` rounds to look
+/// back on, the key is the shorter list of all the outcomes so far, so entries for shorter
+/// lists, down to the empty list for the first move, set how the strategy opens. Any history
+/// missing from the table uses `default`.
+///
+/// This is convenient for importing strategies learned elsewhere as a table of probabilities.
+pub struct MarkovStrategy {
+    pub memory: usize,
+    pub transitions: HashMap<Vec<Outcome>, f64>,
+    pub default: f64,
+    rng: Rng,
+}
+
+impl MarkovStrategy {
+    pub fn new(
+        memory: usize,
+        transitions: HashMap<Vec<Outcome>, f64>,
+        default: f64,
+        seed: u64,
+    ) -> Result<Self, InvalidProbability> {
+        for &p_cooperate in transitions.values() {
+            probability("transitions", p_cooperate)?;
+        }
+        Ok(Self {
+            memory,
+            transitions,
+            default: probability("default", default)?,
+            rng: Rng::new(seed),
+        })
+    }
+}
+
+impl DynStrategy for MarkovStrategy {
+    fn name(&self) -> &str {
+        "Markov"
+    }
+
+    fn description(&self) -> &str {
+        "Cooperates with probabilities that depend on the last few rounds' outcomes."
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+
+    fn next_move(
+        &mut self,
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+    ) -> CooperateOrDefect {
+        let start = my_moves.len().saturating_sub(self.memory);
+        let recent = my_moves[start..]
+            .iter()
+            .zip(&their_moves[start..])
+            .map(|(&my_move, &their_move)| outcome(my_move, their_move))
+            .collect::<Vec<_>>();
+        let p_cooperate = self
+            .transitions
+            .get(&recent)
+            .copied()
+            .unwrap_or(self.default);
+
+        if self.rng.chance(p_cooperate) {
+            Cooperate
+        } else {
+            Defect
+        }
+    }
+}
+
+/// Open with a fixed probe of three cooperations followed by three defections, then cooperate
+/// only while the opponent has cooperated in more than [`Adaptive::THRESHOLD`] of the rounds so
+/// far.
+pub struct Adaptive;
+
+impl Adaptive {
+    /// The moves played before looking at the opponent
+    pub const PROBE: [CooperateOrDefect; 6] =
+        [Cooperate, Cooperate, Cooperate, Defect, Defect, Defect];
+
+    /// The opponent cooperation rate above which Adaptive cooperates
+    pub const THRESHOLD: f64 = 0.5;
+}
+
+impl Strategy for Adaptive {
+    const NAME: &'static str = "Adaptive";
+    const DESCRIPTION: &'static str =
+        "Plays a fixed probe, then cooperates while the opponent mostly cooperates.";
+    type State = ();
+
+    fn next_move(
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+        state: &mut (),
+    ) -> CooperateOrDefect {
+        Self::next_move_with_history(&HistoryView::new(my_moves, their_moves), state)
+    }
+
+    fn next_move_with_history(history: &HistoryView<'_>, _state: &mut ()) -> CooperateOrDefect {
+        if let Some(probe) = Self::PROBE.get(history.rounds()) {
+            return *probe;
+        }
+
+        let cooperations = history.their_cooperations();
+        if cooperations as f64 / history.rounds() as f64 > Self::THRESHOLD {
+            Cooperate
+        } else {
+            Defect
+        }
+    }
+}
+
+/// A Tit for Tat that owns up to its mistakes, which makes it robust to noise.
+///
+/// Each player is in good standing until it defects against an opponent in good standing, and
+/// regains good standing by cooperating. Defecting against an opponent in bad standing is a fair
+/// punishment and does not cost standing. Contrite Tit for Tat only defects when it is in good
+/// standing and the opponent is not. So if one of its own moves was flipped to a defection by
+/// noise, it accepts the opponent's retaliation without retaliating in turn, and mutual
+/// cooperation resumes right away instead of echoing back and forth.
+pub struct ContriteTitForTat;
+
+/// Which players [`ContriteTitForTat`] considers to be in bad standing.
+#[derive(Default)]
+pub struct Standing {
+    me_in_bad_standing: bool,
+    them_in_bad_standing: bool,
+}
+
+impl Strategy for ContriteTitForTat {
+    const NAME: &'static str = "Contrite Tit for Tat";
+    const DESCRIPTION: &'static str =
+        "Tit for Tat that accepts punishment for its own accidental defections.";
+    type State = Standing;
+
+    fn next_move(
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+        standing: &mut Standing,
+    ) -> CooperateOrDefect {
+        if let (Some(my_last), Some(their_last)) = (my_moves.last(), their_moves.last()) {
+            let me_was_bad = standing.me_in_bad_standing;
+            let them_was_bad = standing.them_in_bad_standing;
+            standing.me_in_bad_standing =
+                matches!(my_last, Defect) && (me_was_bad || !them_was_bad);
+            standing.them_in_bad_standing =
+                matches!(their_last, Defect) && (them_was_bad || !me_was_bad);
+        }
+
+        if !standing.me_in_bad_standing && standing.them_in_bad_standing {
+            Defect
+        } else {
+            Cooperate
+        }
+    }
+}
+
+/// Cooperate after every outcome except one: after successfully exploiting the opponent
+/// (defecting while the opponent cooperated) it defects again. It cooperates on the first move,
+/// after mutual cooperation, after being suckered, and after mutual defection.
+pub struct FirmButFair;
+
+impl Strategy for FirmButFair {
+    const NAME: &'static str = "Firm but Fair";
+    const DESCRIPTION: &'static str =
+        "Cooperates after every outcome except successfully exploiting the opponent.";
+    type State = ();
+
+    fn next_move(
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+        _state: &mut (),
+    ) -> CooperateOrDefect {
+        match (my_moves.last(), their_moves.last()) {
+            (Some(Defect), Some(Cooperate)) => Defect,
+            _ => Cooperate,
+        }
+    }
+}
+
+/// Do the opposite of whatever the opponent did last turn, opening with a defection.
+pub struct ReverseTitForTat;
+
+impl Strategy for ReverseTitForTat {
+    const NAME: &'static str = "Reverse Tit for Tat";
+    const DESCRIPTION: &'static str =
+        "Defects first, then plays the opposite of the opponent's last move.";
+    type State = ();
+
+    fn next_move(
+        _my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+        _state: &mut (),
+    ) -> CooperateOrDefect {
+        match their_moves.last() {
+            None => Defect,
+            Some(their_last) => their_last.opposite(),
+        }
+    }
+}
+
+/// Ignore the opponent and alternate Cooperate, Defect, Cooperate, Defect, ...
+pub struct Alternator;
+
+impl Strategy for Alternator {
+    const NAME: &'static str = "Alternator";
+    const DESCRIPTION: &'static str =
+        "Alternates cooperating and defecting, ignoring the opponent.";
+    type State = ();
+
+    fn next_move(
+        my_moves: &[CooperateOrDefect],
+        _their_moves: &[CooperateOrDefect],
+        _state: &mut (),
+    ) -> CooperateOrDefect {
+        if my_moves.len().is_multiple_of(2) {
+            Cooperate
+        } else {
+            Defect
+        }
+    }
+}
+
+/// Open with a secret code and then check whether the opponent opened with the same code. If it
+/// did, the opponent is treated as kin and Handshake cooperates for the rest of the game;
+/// otherwise it defects for the rest of the game. This lets copies of a strategy recognise and
+/// support each other in a tournament.
+pub struct Handshake;
+
+impl Handshake {
+    /// The opening that Handshake plays and looks for in its opponent
+    pub const SECRET: [CooperateOrDefect; 4] = [Defect, Cooperate, Defect, Defect];
+}
+
+impl Strategy for Handshake {
+    const NAME: &'static str = "Handshake";
+    const DESCRIPTION: &'static str =
+        "Opens with a secret code and cooperates only with opponents that answer it.";
+    type State = ();
+
+    fn next_move(
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+        _state: &mut (),
+    ) -> CooperateOrDefect {
+        if let Some(code) = Self::SECRET.get(my_moves.len()) {
+            return *code;
+        }
+
+        let is_kin = Self::SECRET
+            .iter()
+            .zip(their_moves)
+            .all(|pair| matches!(pair, (Cooperate, Cooperate) | (Defect, Defect)));
+        if is_kin {
+            Cooperate
+        } else {
+            Defect
+        }
+    }
+}
+
+/// Tit for Tat with two counters watching the recent history. The deadlock counter grows each
+/// time the opponent changes its move while Omega Tit for Tat is copying it; once it reaches
+/// [`OmegaTitForTat::DEADLOCK_THRESHOLD`] the players are taken to be stuck echoing each other's
+/// defections, and Omega Tit for Tat cooperates to break the cycle. The randomness counter grows
+/// whenever the opponent changes its move or the two players' moves differ, and shrinks after
+/// the opponent cooperates twice in a row; once it reaches [`OmegaTitForTat::RANDOMNESS_THRESHOLD`]
+/// the opponent is taken to be playing at random and Omega Tit for Tat defects for the rest of
+/// the game.
+///
+/// A deadlock is broken by cooperating twice in a row. Against Alternator that happens on the
+/// seventh and eighth moves, where the first would have been a defection under Tit for Tat,
+/// and against Suspicious Tit for Tat it ends the echo for good. Random opponents are soon met
+/// with permanent defection:
+///
+/// ```
+/// # use prisoners_dilemma_competition::{CooperateOrDefect::{self, *}, *};
+/// const C: CooperateOrDefect = Cooperate;
+/// const D: CooperateOrDefect = Defect;
+/// let mut game = RepeatedPrisonersDilemma::<OmegaTitForTat, Alternator>::new();
+/// game.play_n_rounds(8);
+/// assert_eq!(game.player_1_moves(), [C, C, D, C, D, C, C, C]);
+///
+/// let mut game = RepeatedPrisonersDilemma::<OmegaTitForTat, SuspiciousTitForTat>::new();
+/// game.play_n_rounds(20);
+/// assert_eq!(game.player_1_moves()[..8], [C, D, C, D, C, C, C, C]);
+/// assert!(game.outcomes()[7..].iter().all(|&o| o == Outcome::MutualCooperation));
+///
+/// for seed in 0..3 {
+///     let mut game = DynGame::new(
+///         StrategyInstance::<OmegaTitForTat>::boxed(),
+///         Box::new(Random::new(0.5, seed).unwrap()),
+///     );
+///     game.play_n_rounds(100);
+///     assert!(game.player_1_moves()[20..].iter().all(|&m| m == Defect), "seed {seed}");
+/// }
+/// ```
+pub struct OmegaTitForTat;
+
+impl OmegaTitForTat {
+    /// The deadlock counter value at which Omega Tit for Tat cooperates to break a deadlock
+    pub const DEADLOCK_THRESHOLD: usize = 3;
+
+    /// The randomness counter value at which Omega Tit for Tat gives up and always defects
+    pub const RANDOMNESS_THRESHOLD: isize = 8;
+}
+
+/// The counters kept by [`OmegaTitForTat`].
+#[derive(Default)]
+pub struct OmegaCounters {
+    deadlock: usize,
+    randomness: isize,
+}
+
+impl Strategy for OmegaTitForTat {
+    const NAME: &'static str = "Omega Tit for Tat";
+    const DESCRIPTION: &'static str =
+        "Tit for Tat that breaks deadlocks and gives up on random opponents.";
+    const AUTHOR: &'static str = "Slany and Kienreich";
+    type State = OmegaCounters;
+
+    fn next_move(
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+        counters: &mut OmegaCounters,
+    ) -> CooperateOrDefect {
+        let (my_last, their_previous, their_last) = match (my_moves, their_moves) {
+            (_, []) => return Cooperate,
+            (_, [_]) => return TitForTat::next_move(my_moves, their_moves, &mut ()),
+            ([.., my_last], [.., their_previous, their_last]) => {
+                (my_last, their_previous, their_last)
+            }
+            _ => unreachable!("the histories are the same length"),
+        };
+        if counters.randomness >= Self::RANDOMNESS_THRESHOLD {
+            return Defect;
+        }
+
+        let same = |a: &CooperateOrDefect, b: &CooperateOrDefect| {
+            matches!((a, b), (Cooperate, Cooperate) | (Defect, Defect))
+        };
+        let they_switched = !same(their_previous, their_last);
+
+        if counters.deadlock >= Self::DEADLOCK_THRESHOLD {
+            // Cooperate twice in a row to break the deadlock, once on reaching the threshold
+            // and again on the next move, then give Tit for Tat a fresh start
+            counters.deadlock = if counters.deadlock == Self::DEADLOCK_THRESHOLD {
+                Self::DEADLOCK_THRESHOLD + 1
+            } else {
+                0
+            };
+            return Cooperate;
+        }
+
+        if matches!((their_previous, their_last), (Cooperate, Cooperate)) {
+            counters.randomness -= 1;
+        }
+        if they_switched {
+            counters.randomness += 1;
+        }
+        if !same(my_last, their_last) {
+            counters.randomness += 1;
+        }
+        if counters.randomness >= Self::RANDOMNESS_THRESHOLD {
+            return Defect;
+        }
+
+        if they_switched {
+            counters.deadlock += 1;
+        } else {
+            counters.deadlock = 0;
+        }
+        TitForTat::next_move(my_moves, their_moves, &mut ())
+    }
+}
+
+/// Defect on the first move to test the opponent, then cooperate on the second while waiting to
+/// see the opponent's reply. If the opponent retaliated on its second move, Tester apologizes by
+/// cooperating on moves 3 and 4 and plays Tit for Tat from then on. If the opponent let the
+/// defection go, Tester exploits it by cooperating on odd moves and defecting on even ones for
+/// the rest of the game.
+pub struct Tester;
+
+impl Strategy for Tester {
+    const NAME: &'static str = "Tester";
+    const DESCRIPTION: &'static str =
+        "Defects first to test the opponent, then apologizes or exploits it.";
+    const AUTHOR: &'static str = "David Gladstein";
+    type State = ();
+
+    fn next_move(
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+        _state: &mut (),
+    ) -> CooperateOrDefect {
+        match (my_moves.len(), their_moves) {
+            (0, _) => Defect,
+            (1, _) => Cooperate,
+            (2 | 3, [_, Defect, ..]) => Cooperate,
+            (_, [_, Defect, ..]) => TitForTat::next_move(my_moves, their_moves, &mut ()),
+            (round, _) if round.is_multiple_of(2) => Cooperate,
+            _ => Defect,
+        }
+    }
+}
+
+/// The forgiving copycat from Nicky Case's The Evolution of Trust: it cooperates, shrugs off a
+/// single defection, and cheats back whenever the opponent's last two moves were both
+/// defections. That is the rule of [`TitForTwoTats`], so Copykitten is another name for it,
+/// which can also be looked up by that name.
+///
+/// Forgiving single defections keeps it out of the feuds that a mistake starts between two
+/// copies of [`TitForTat`]:
+///
+/// ```
+/// # use prisoners_dilemma_competition::{CooperateOrDefect::*, *};
+/// assert_moves::<Copykitten>(&[
+///     (&[], &[], Cooperate),
+///     (&[Cooperate], &[Defect], Cooperate),
+///     (&[Cooperate, Cooperate], &[Defect, Defect], Defect),
+///     (&[Cooperate, Cooperate, Cooperate], &[Defect, Cooperate, Defect], Cooperate),
+/// ]);
+///
+/// let mut kittens = RepeatedPrisonersDilemma::<Copykitten, Copykitten>::new();
+/// kittens.set_noise(0.05, 1);
+/// kittens.play_n_rounds(1000);
+/// let mut copycats = RepeatedPrisonersDilemma::<TitForTat, TitForTat>::new();
+/// copycats.set_noise(0.05, 1);
+/// copycats.play_n_rounds(1000);
+/// assert!(kittens.calculate_score().0 > copycats.calculate_score().0);
+/// assert!(kittens.calculate_score().1 > copycats.calculate_score().1);
+///
+/// let player: Box<dyn DynStrategy> = "copykitten".parse()?;
+/// assert_eq!(player.name(), "Tit for Two Tats");
+/// # Ok::<(), UnknownStrategy>(())
+/// ```
+pub type Copykitten = TitForTwoTats;
+
+/// The detective from Nicky Case's The Evolution of Trust. It opens with C, D, C, C to see how
+/// the opponent reacts. If the opponent defected at any point during those four moves it plays
+/// Tit for Tat from then on, and if the opponent never defected it defects for the rest of the
+/// game to exploit it.
+pub struct Detective;
+
+impl Detective {
+    /// The moves played before deciding how to treat the opponent
+    pub const OPENING: [CooperateOrDefect; 4] = [Cooperate, Defect, Cooperate, Cooperate];
+}
+
+impl Strategy for Detective {
+    const NAME: &'static str = "Detective";
+    const DESCRIPTION: &'static str =
+        "Opens with C, D, C, C, then exploits opponents that never defected and copies the rest.";
+    const AUTHOR: &'static str = "Nicky Case";
+    type State = ();
+
+    fn next_move(
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+        _state: &mut (),
+    ) -> CooperateOrDefect {
+        if let Some(opening) = Self::OPENING.get(my_moves.len()) {
+            return *opening;
+        }
+
+        let retaliated = their_moves[..Self::OPENING.len()]
+            .iter()
+            .any(|m| matches!(m, Defect));
+        if retaliated {
+            TitForTat::next_move(my_moves, their_moves, &mut ())
+        } else {
+            Defect
+        }
+    }
+}
+
+/// Rapoport and Chammah's Simpleton, the win-stay, lose-shift rule stated in terms of the
+/// opponent alone: a round is a win if the opponent cooperated (CC or DC) and a loss if it
+/// defected (CD or DD). After a win Simpleton repeats its own last move, and after a loss it
+/// switches. It cooperates on the first move.
+///
+/// [`Pavlov`] instead judges each round by how well it did, counting the reward and the
+/// temptation as good outcomes. Those are exactly the rounds in which the opponent cooperated,
+/// so in any genuine prisoners' dilemma the two strategies play the same moves, and Simpleton is
+/// another name for Pavlov, which can also be looked up by that name.
+///
+/// ```
+/// # use prisoners_dilemma_competition::{CooperateOrDefect::*, *};
+/// assert_moves::<Simpleton>(&[
+///     (&[], &[], Cooperate),
+///     (&[Cooperate], &[Cooperate], Cooperate),
+///     (&[Cooperate], &[Defect], Defect),
+///     (&[Defect], &[Cooperate], Defect),
+///     (&[Defect], &[Defect], Cooperate),
+/// ]);
+/// let player: Box<dyn DynStrategy> = "simpleton".parse()?;
+/// assert_eq!(player.name(), "Pavlov");
+/// # Ok::<(), UnknownStrategy>(())
+/// ```
+pub type Simpleton = Pavlov;
+
+/// Opens with C, C, D, D, D, D to find out whether the opponent stands up for itself. If the
+/// opponent defected in reply to any of those defections it is treated as dangerous and
+/// appeased with cooperation for the rest of the game, and if it let them all pass it is a
+/// pushover and is defected against for the rest of the game. Only the replies to the first
+/// three defections have been seen when the decision is made on move 7.
+pub struct GradualKiller;
+
+impl GradualKiller {
+    /// The moves played before deciding how to treat the opponent
+    pub const OPENING: [CooperateOrDefect; 6] =
+        [Cooperate, Cooperate, Defect, Defect, Defect, Defect];
+}
+
+impl Strategy for GradualKiller {
+    const NAME: &'static str = "Gradual Killer";
+    const DESCRIPTION: &'static str =
+        "Opens with C, C, D, D, D, D, then cooperates if the opponent hit back and defects if not.";
+    type State = ();
+
+    fn next_move(
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+        _state: &mut (),
+    ) -> CooperateOrDefect {
+        if let Some(opening) = Self::OPENING.get(my_moves.len()) {
+            return *opening;
+        }
+
+        // The opponent's replies to the defections in the opening, from the round after the
+        // first one up to the end of the opening
+        let first_defection = Self::OPENING.iter().position(|&m| m == Defect).unwrap();
+        let retaliated = their_moves[first_defection + 1..Self::OPENING.len()].contains(&Defect);
+        if retaliated {
+            Cooperate
+        } else {
+            Defect
+        }
+    }
+}
+
+/// Play whichever move the opponent has made more often over the whole game, cooperating when
+/// it has made both equally often, including before the first move. This is meant as a copy of
+/// the opponent's habits rather than a verdict on them, but it makes the same moves as
+/// [`SoftMajority`], so Mimic is another name for it, which can also be looked up by that name.
+///
+/// ```
+/// # use prisoners_dilemma_competition::{CooperateOrDefect::{self, *}, *};
+/// const C: CooperateOrDefect = Cooperate;
+/// const D: CooperateOrDefect = Defect;
+/// assert_moves::<Mimic>(&[
+///     (&[], &[], Cooperate),
+///     (&[C, C, C], &[C, D, C], Cooperate),
+///     (&[C, C, D], &[D, D, C], Defect),
+///     (&[C, D], &[D, C], Cooperate),
+/// ]);
+/// let player: Box<dyn DynStrategy> = "mimic".parse()?;
+/// assert_eq!(player.name(), "Soft Majority");
+/// # Ok::<(), UnknownStrategy>(())
+/// ```
+pub type Mimic = SoftMajority;
@@ -0,0 +1,68 @@
+//! Helpers for checking that a strategy behaves as intended.
+
+use crate::{CooperateOrDefect, Strategy};
+
+/// Check the move that the strategy `S` picks in each of the `scenarios`, given as
+/// `(my_moves, their_moves, expected_move)`. Each scenario starts from a fresh state, which is
+/// built up by showing the strategy every earlier round of the history in turn, as a game would,
+/// before the move for the whole history is checked. Rather than stopping at the first wrong
+/// move, every scenario is checked and all of the failures are reported together.
+///
+/// ```
+/// # use prisoners_dilemma_competition::{CooperateOrDefect::{self, *}, *};
+/// assert_moves::<TitForTat>(&[
+///     (&[], &[], Cooperate),
+///     (&[Cooperate], &[Cooperate], Cooperate),
+///     (&[Cooperate], &[Defect], Defect),
+///     (&[Cooperate, Defect], &[Defect, Cooperate], Cooperate),
+///     (&[Defect, Defect], &[Defect, Defect], Defect),
+/// ]);
+///
+/// // Gradual is still punishing the second defection, which it only knows from the earlier rounds
+/// const C: CooperateOrDefect = Cooperate;
+/// const D: CooperateOrDefect = Defect;
+/// assert_moves::<Gradual>(&[(&[C, D, C, C, C, D], &[D, C, C, C, D, C], Defect)]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the strategy picks the wrong move in any scenario, listing every such scenario.
+pub fn assert_moves<S: Strategy>(
+    scenarios: &[(
+        &[CooperateOrDefect],
+        &[CooperateOrDefect],
+        CooperateOrDefect,
+    )],
+) {
+    let history =
+        |moves: &[CooperateOrDefect]| moves.iter().map(|m| m.as_char()).collect::<String>();
+
+    let failures = scenarios
+        .iter()
+        .enumerate()
+        .filter_map(|(i, (my_moves, their_moves, expected))| {
+            let mut state = S::State::default();
+            for round in 0..my_moves.len().min(their_moves.len()) {
+                S::next_move(&my_moves[..round], &their_moves[..round], &mut state);
+            }
+            let actual = S::next_move(my_moves, their_moves, &mut state);
+            (actual != *expected).then(|| {
+                format!(
+                    "  scenario {i}: my moves [{}], their moves [{}]: expected {expected}, got {actual}",
+                    history(my_moves),
+                    history(their_moves),
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+
+    if !failures.is_empty() {
+        panic!(
+            "{} picked the wrong move in {} of {} scenarios:\n{}",
+            S::NAME,
+            failures.len(),
+            scenarios.len(),
+            failures.join("\n")
+        );
+    }
+}
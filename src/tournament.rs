@@ -0,0 +1,806 @@
+//! Round-robin tournaments in which every strategy plays every other strategy.
+
+use std::{
+    collections::HashMap,
+    future::{self, Future},
+    num::NonZeroUsize,
+    pin::Pin,
+    sync::atomic::{AtomicUsize, Ordering},
+    task::{Context, Poll},
+    thread,
+};
+
+use crate::{
+    available_strategies, registry, stats, CooperateOrDefect, CooperateOrDefect::*, DynGame,
+    DynStrategy, GameResult, PayoffMatrix, Rng, Strategy, StrategyInstance, TournamentRecord,
+    NUM_TURNS,
+};
+
+/// A strategy that has been entered into a tournament.
+///
+/// Strategies are type parameters everywhere else, which makes it impossible to keep several
+/// different ones in a single list. An entrant erases the type, keeping only the name and a way
+/// to create a fresh player for each game.
+pub struct Entrant {
+    name: &'static str,
+    /// Creates a player from the seed for its random choices
+    new_player: Box<dyn Fn(u64) -> Box<dyn DynStrategy> + Send + Sync>,
+    /// Whether the players share memory that outlasts their games
+    shares_state: bool,
+}
+
+impl Entrant {
+    pub fn new<S>() -> Self
+    where
+        S: Strategy + 'static,
+        S::State: 'static,
+    {
+        Self {
+            name: S::NAME,
+            new_player: Box::new(|_| StrategyInstance::<S>::boxed()),
+            shares_state: false,
+        }
+    }
+
+    /// An entrant for a strategy that is not a [`Strategy`] type, such as one from the
+    /// [`registry`](crate::registry).
+    pub fn from_fn(name: &'static str, new_player: fn() -> Box<dyn DynStrategy>) -> Self {
+        Self {
+            name,
+            new_player: Box::new(move |_| new_player()),
+            shares_state: false,
+        }
+    }
+
+    /// An entrant for a strategy that makes random choices. `new_player` is given a different
+    /// seed for each game, derived from the tournament's seed, so that seeded tournaments can be
+    /// replayed exactly.
+    pub fn seeded<F>(name: &'static str, new_player: F) -> Self
+    where
+        F: Fn(u64) -> Box<dyn DynStrategy> + Send + Sync + 'static,
+    {
+        Self {
+            name,
+            new_player: Box::new(new_player),
+            shares_state: false,
+        }
+    }
+
+    /// Mark the entrant's players as sharing memory between their games.
+    pub(crate) fn sharing_state(self) -> Self {
+        Self {
+            shares_state: true,
+            ..self
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Whether the entrant's players share memory that lasts from one game to the next, as
+    /// [`Entrant::field_aware`] and [`FieldTitForTat::entrant`](crate::FieldTitForTat::entrant)
+    /// do. This is not known for entrants made with [`Entrant::seeded`] from closures that keep
+    /// their own state, which are assumed not to.
+    pub fn shares_state(&self) -> bool {
+        self.shares_state
+    }
+
+    /// Whether the entrant's players always make the same move given the same history, see
+    /// [`DynStrategy::is_deterministic`].
+    pub fn is_deterministic(&self) -> bool {
+        self.new_player(0).is_deterministic()
+    }
+
+    /// A fresh player following this entrant's strategy, ready to start a new game. Entrants
+    /// that make random choices use `seed` to make them.
+    pub fn new_player(&self, seed: u64) -> Box<dyn DynStrategy> {
+        (self.new_player)(seed)
+    }
+}
+
+/// Settings for a tournament.
+///
+/// Every game gets its own seeds for the players' and the noise's random choices. They are
+/// derived from `seed` and the positions of the two entrants in the list, so running the same
+/// tournament with the same seed gives exactly the same results, whether or not the games are
+/// played in parallel.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TournamentConfig {
+    pub seed: u64,
+    /// The number of rounds in each game, where 0 scores every game 0 to 0 and every entrant ties
+    pub rounds: usize,
+    /// The chance that each move is flipped by mistake, see
+    /// [`RepeatedPrisonersDilemma::set_noise`](crate::RepeatedPrisonersDilemma::set_noise)
+    pub noise: f64,
+    /// Whether each entrant also plays a copy of itself
+    pub self_play: bool,
+    /// Whether each pair of entrants plays twice, once in each position, and the two games'
+    /// scores are added together. The rematch gets its own seeds, so in tournaments with noise
+    /// or random strategies this also evens out the luck of the first game.
+    pub double_round_robin: bool,
+    /// How entrants with equal total scores are ordered in the results
+    pub tie_break: TieBreak,
+}
+
+/// How to order entrants that finish a tournament with the same total score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TieBreak {
+    /// Keep them in the order they were entered
+    #[default]
+    EntryOrder,
+    /// The entrant that cooperated in more of its moves, across all of its games, comes first
+    CooperationRate,
+    /// The entrant that outscored the others it is tied with, in the games between them, comes
+    /// first. With more than two tied entrants, each one's margins against all of the others are
+    /// added up. Any that are still level keep the order they were entered in.
+    HeadToHead,
+    /// Alphabetical order of their names
+    Name,
+}
+
+impl Default for TournamentConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            rounds: NUM_TURNS,
+            noise: 0.0,
+            self_play: false,
+            double_round_robin: false,
+            tie_break: TieBreak::EntryOrder,
+        }
+    }
+}
+
+/// Play every entrant against every other entrant for `NUM_TURNS` rounds and total up the
+/// scores each one accumulated across all of its games.
+///
+/// When `self_play` is set, each entrant also plays a game against a copy of itself. Only
+/// player 1's score from that game is counted so that the strategy is not credited twice.
+///
+/// The results are sorted from highest to lowest total score. Entrants with equal scores keep
+/// the order in which they were entered.
+///
+/// With the default payoffs of 10 each for mutual cooperation, 20 and -5 when only one player
+/// defects, and 2 each for mutual defection, the totals over 200 rounds work out as:
+///
+/// ```
+/// # use prisoners_dilemma_competition::*;
+/// let entrants = [
+///     Entrant::new::<AlwaysCooperate>(),
+///     Entrant::new::<AlwaysDefect>(),
+///     Entrant::new::<TitForTat>(),
+/// ];
+/// assert_eq!(
+///     run_tournament(&entrants, false),
+///     [
+///         ("Always Defect", 200 * 20 + 20 + 199 * 2),
+///         ("Tit for Tat", 200 * 10 - 5 + 199 * 2),
+///         ("Always Cooperate", 200 * 10 - 200 * 5),
+///     ]
+/// );
+/// ```
+pub fn run_tournament(entrants: &[Entrant], self_play: bool) -> Vec<(&'static str, isize)> {
+    run_tournament_with_config(
+        entrants,
+        &TournamentConfig {
+            self_play,
+            ..TournamentConfig::default()
+        },
+    )
+}
+
+/// The same as [`run_tournament`], but with the number of rounds, noise, and seed taken from
+/// `config`. Entrants with equal scores are ordered by `config.tie_break`.
+pub fn run_tournament_with_config(
+    entrants: &[Entrant],
+    config: &TournamentConfig,
+) -> Vec<(&'static str, isize)> {
+    run_tournament_with_progress(entrants, config, |_, _| {})
+}
+
+/// The same as [`run_tournament_with_config`], but `progress` is called after every game with
+/// the number of games finished so far and the total number of games, for showing a progress
+/// bar during long tournaments. Unless there are no games at all, the last call reports every
+/// game as finished.
+pub fn run_tournament_with_progress<F>(
+    entrants: &[Entrant],
+    config: &TournamentConfig,
+    mut progress: F,
+) -> Vec<(&'static str, isize)>
+where
+    F: FnMut(usize, usize),
+{
+    let pairings = pairings(entrants.len(), config.self_play, config.double_round_robin);
+    let games = pairings
+        .iter()
+        .enumerate()
+        .map(|(game, &(i, j))| {
+            let played = Played::of(&play_seeded_game(entrants, i, j, config));
+            progress(game + 1, pairings.len());
+            played
+        })
+        .collect::<Vec<_>>();
+
+    tally(entrants, &pairings, &games, config.tie_break)
+}
+
+/// Play the tournament described by `config` one game at a time, yielding each game's summary
+/// as soon as it has been played, for showing results live. The games are played in the same
+/// order and with the same seeds as [`run_tournament_with_config`], and only when the next
+/// result is asked for.
+pub fn tournament_stream<'a>(
+    entrants: &'a [Entrant],
+    config: &TournamentConfig,
+) -> TournamentStream<'a> {
+    TournamentStream {
+        entrants,
+        config: *config,
+        pairings: pairings(entrants.len(), config.self_play, config.double_round_robin).into_iter(),
+    }
+}
+
+/// Plays a tournament lazily, see [`tournament_stream`].
+pub struct TournamentStream<'a> {
+    entrants: &'a [Entrant],
+    config: TournamentConfig,
+    /// The games that have not been played yet
+    pairings: std::vec::IntoIter<(usize, usize)>,
+}
+
+impl Iterator for TournamentStream<'_> {
+    type Item = GameResult;
+
+    fn next(&mut self) -> Option<GameResult> {
+        let (i, j) = self.pairings.next()?;
+        Some(play_seeded_game(self.entrants, i, j, &self.config).result())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.pairings.size_hint()
+    }
+}
+
+impl ExactSizeIterator for TournamentStream<'_> {}
+
+/// The same as [`run_tournament`], but the games are spread across all of the machine's cores.
+///
+/// Every game creates fresh players, so the results are identical to the sequential version no
+/// matter how the games are scheduled.
+pub fn run_tournament_parallel(
+    entrants: &[Entrant],
+    self_play: bool,
+) -> Vec<(&'static str, isize)> {
+    run_tournament_parallel_with_config(
+        entrants,
+        &TournamentConfig {
+            self_play,
+            ..TournamentConfig::default()
+        },
+    )
+}
+
+/// The same as [`run_tournament_with_config`], but the games are spread across all of the
+/// machine's cores. The results are identical to the sequential version for the same config.
+pub fn run_tournament_parallel_with_config(
+    entrants: &[Entrant],
+    config: &TournamentConfig,
+) -> Vec<(&'static str, isize)> {
+    let pairings = pairings(entrants.len(), config.self_play, config.double_round_robin);
+    let next_game = AtomicUsize::new(0);
+    let workers = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+
+    let mut games = vec![Played::default(); pairings.len()];
+    thread::scope(|scope| {
+        let handles = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut finished = Vec::new();
+                    loop {
+                        let game = next_game.fetch_add(1, Ordering::Relaxed);
+                        let Some(&(i, j)) = pairings.get(game) else {
+                            return finished;
+                        };
+                        let played = Played::of(&play_seeded_game(entrants, i, j, config));
+                        finished.push((game, played));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for handle in handles {
+            for (game, played) in handle.join().expect("tournament worker panicked") {
+                games[game] = played;
+            }
+        }
+    });
+
+    tally(entrants, &pairings, &games, config.tie_break)
+}
+
+/// The same as [`run_tournament_with_config`], for use inside an async application such as a
+/// web server. The results are identical to the sequential version for the same config, except
+/// with field aware entrants, as with the parallel tournaments.
+///
+/// This does not depend on any particular runtime. Every game is its own future, and they are
+/// all played together on the task that awaits the tournament: each time it is polled, every
+/// unfinished game plays one more round, and then control goes back to the executor so that a
+/// long tournament does not hold up other tasks.
+pub async fn run_tournament_async(
+    entrants: &[Entrant],
+    config: &TournamentConfig,
+) -> Vec<(&'static str, isize)> {
+    let pairings = pairings(entrants.len(), config.self_play, config.double_round_robin);
+    let mut playing = pairings
+        .iter()
+        .map(|&(i, j)| GameFuture {
+            game: seeded_game(entrants, i, j, config),
+            rounds: config.rounds,
+        })
+        .collect::<Vec<_>>();
+    let mut games = vec![None; pairings.len()];
+
+    future::poll_fn(|cx| {
+        let mut finished = true;
+        for (game, played) in playing.iter_mut().zip(&mut games) {
+            if played.is_none() {
+                match Pin::new(game).poll(cx) {
+                    Poll::Ready(result) => *played = Some(result),
+                    Poll::Pending => finished = false,
+                }
+            }
+        }
+        if finished {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    })
+    .await;
+
+    let games = games.into_iter().flatten().collect::<Vec<_>>();
+    tally(entrants, &pairings, &games, config.tie_break)
+}
+
+/// A game of a tournament that plays one round each time it is polled, see
+/// [`run_tournament_async`].
+struct GameFuture {
+    game: DynGame,
+    rounds: usize,
+}
+
+impl Future for GameFuture {
+    type Output = Played;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Played> {
+        if self.game.player_1_moves().len() < self.rounds {
+            self.game.play_next_round();
+        }
+        if self.game.player_1_moves().len() < self.rounds {
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        } else {
+            Poll::Ready(Played::of(&self.game))
+        }
+    }
+}
+
+/// A strategy's average total score over several runs of a tournament, together with a 95%
+/// confidence interval for it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreEstimate {
+    pub name: &'static str,
+    pub mean: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+/// Run the tournament described by `config` `trials` times, using the seeds `config.seed`,
+/// `config.seed + 1`, and so on, and estimate each entrant's expected total score.
+///
+/// The confidence intervals use the normal approximation, the mean plus or minus 1.96 standard
+/// errors. With fewer than two trials there is no way to estimate the spread, so the interval is
+/// just the mean. The estimates are sorted from highest to lowest mean, with ties kept in the
+/// order the entrants were entered.
+pub fn run_tournament_repeated(
+    entrants: &[Entrant],
+    config: &TournamentConfig,
+    trials: usize,
+) -> Vec<ScoreEstimate> {
+    let seeds = (0..trials)
+        .map(|trial| config.seed.wrapping_add(trial as u64))
+        .collect::<Vec<_>>();
+    let pairings = pairings(entrants.len(), config.self_play, config.double_round_robin);
+    let scores = play_with_seeds(entrants, &pairings, config, &seeds);
+
+    let mut totals = vec![Vec::with_capacity(trials); entrants.len()];
+    for scores in &scores {
+        for (entrant, &(_, total)) in totals
+            .iter_mut()
+            .zip(&tally_unsorted(entrants, &pairings, scores))
+        {
+            entrant.push(total as f64);
+        }
+    }
+
+    let mut estimates = entrants
+        .iter()
+        .zip(&totals)
+        .map(|(entrant, totals)| {
+            let mean = stats::mean(totals).unwrap_or(0.0);
+            let half_width = stats::std_dev(totals)
+                .map_or(0.0, |std_dev| 1.96 * std_dev / (totals.len() as f64).sqrt());
+            ScoreEstimate {
+                name: entrant.name,
+                mean,
+                lower: mean - half_width,
+                upper: mean + half_width,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    estimates.sort_by(|a, b| b.mean.total_cmp(&a.mean));
+    estimates
+}
+
+/// Run the tournament described by `config` once for each of the `seeds`, in place of
+/// `config.seed`, and collect every entrant's total score under each one, in the order of
+/// `seeds`. Unlike [`run_tournament_repeated`] this keeps the raw totals, for studying how much
+/// the rankings depend on luck.
+pub fn seed_sweep(
+    entrants: &[Entrant],
+    config: &TournamentConfig,
+    seeds: &[u64],
+) -> HashMap<String, Vec<isize>> {
+    let mut totals: HashMap<String, Vec<isize>> = entrants
+        .iter()
+        .map(|entrant| (entrant.name.to_string(), Vec::with_capacity(seeds.len())))
+        .collect();
+    let pairings = pairings(entrants.len(), config.self_play, config.double_round_robin);
+    let scores = play_with_seeds(entrants, &pairings, config, seeds);
+    for scores in &scores {
+        for (name, total) in tally_unsorted(entrants, &pairings, scores) {
+            totals.get_mut(name).unwrap().push(total);
+        }
+    }
+
+    totals
+}
+
+/// Run the tournament described by `config` once for each of the `seeds`, as in [`seed_sweep`],
+/// and summarize where each entrant finished: its mean rank and the standard deviation of its
+/// rank across the seeds. Ranks count from 1, and entrants with equal totals share a rank as in
+/// [`ranking`](crate::ranking). With fewer than two seeds the standard deviations are 0.
+///
+/// An entrant that finishes near the top under every seed is a more convincing winner than one
+/// with the best mean score thanks to a few lucky runs. The results are (name, mean rank,
+/// standard deviation), sorted from the best mean rank to the worst, with ties kept in the
+/// order the entrants were entered.
+pub fn rank_statistics(
+    entrants: &[Entrant],
+    config: &TournamentConfig,
+    seeds: &[u64],
+) -> Vec<(String, f64, f64)> {
+    let pairings = pairings(entrants.len(), config.self_play, config.double_round_robin);
+    let scores = play_with_seeds(entrants, &pairings, config, seeds);
+
+    let mut ranks = vec![Vec::with_capacity(seeds.len()); entrants.len()];
+    for scores in &scores {
+        let totals = tally_unsorted(entrants, &pairings, scores);
+        for (entrant, &(_, total)) in ranks.iter_mut().zip(&totals) {
+            let ahead = totals.iter().filter(|&&(_, other)| other > total).count();
+            entrant.push((ahead + 1) as f64);
+        }
+    }
+
+    let mut statistics = entrants
+        .iter()
+        .zip(&ranks)
+        .map(|(entrant, ranks)| {
+            (
+                entrant.name.to_string(),
+                stats::mean(ranks).unwrap_or(0.0),
+                stats::std_dev(ranks).unwrap_or(0.0),
+            )
+        })
+        .collect::<Vec<_>>();
+    statistics.sort_by(|a, b| a.1.total_cmp(&b.1));
+    statistics
+}
+
+/// Play the games in `pairings` with the settings in `config` once for each of the `seeds`, in
+/// place of `config.seed`, and return the scores of the games under each seed.
+///
+/// Without noise, a game between two deterministic entrants comes out the same whatever the
+/// seed, so each of those is only played once and its score reused for every seed.
+fn play_with_seeds(
+    entrants: &[Entrant],
+    pairings: &[(usize, usize)],
+    config: &TournamentConfig,
+    seeds: &[u64],
+) -> Vec<Vec<(isize, isize)>> {
+    let deterministic = entrants
+        .iter()
+        .map(Entrant::is_deterministic)
+        .collect::<Vec<_>>();
+    let fixed_scores = pairings
+        .iter()
+        .map(|&(i, j)| {
+            (config.noise == 0.0 && deterministic[i] && deterministic[j])
+                .then(|| play_seeded_game(entrants, i, j, config).calculate_score())
+        })
+        .collect::<Vec<_>>();
+
+    seeds
+        .iter()
+        .map(|&seed| {
+            let config = TournamentConfig { seed, ..*config };
+            pairings
+                .iter()
+                .zip(&fixed_scores)
+                .map(|(&(i, j), fixed)| {
+                    fixed.unwrap_or_else(|| {
+                        play_seeded_game(entrants, i, j, &config).calculate_score()
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// How a single strategy fared against each of the built-in strategies, see [`run_gauntlet`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GauntletResult {
+    /// The challenger's score summed over all of its games
+    pub total: isize,
+    /// The challenger's score against each opponent, in the order the strategies are registered
+    pub opponents: Vec<(&'static str, isize)>,
+}
+
+/// Play `challenger` against every strategy in the [`registry`](crate::registry), including a
+/// copy of itself if it is registered, for `NUM_TURNS` rounds each. The challenger is always
+/// player 1. This is much quicker than a full tournament when all you want to know is how one
+/// new strategy does.
+pub fn run_gauntlet(challenger: &Entrant) -> GauntletResult {
+    let registry = registry();
+    let opponents = available_strategies()
+        .into_iter()
+        .map(|name| {
+            let (score, _) = play_game(challenger, &Entrant::from_fn(name, registry[name]));
+            (name, score)
+        })
+        .collect::<Vec<_>>();
+    let total = opponents
+        .iter()
+        .fold(0, |total: isize, &(_, score)| total.saturating_add(score));
+
+    GauntletResult { total, opponents }
+}
+
+/// Play the same games as [`run_tournament`] but keep every game's full history, along with the
+/// settings needed to score them.
+pub fn record_tournament(entrants: &[Entrant], self_play: bool) -> TournamentRecord {
+    let games = pairings(entrants.len(), self_play, false)
+        .into_iter()
+        .map(|(i, j)| {
+            let mut game = new_game(&entrants[i], &entrants[j]);
+            game.play();
+            game.into_record()
+        })
+        .collect();
+
+    TournamentRecord {
+        rounds: NUM_TURNS,
+        payoffs: PayoffMatrix::default(),
+        games,
+    }
+}
+
+/// Play the same games as [`run_tournament`] and summarize each one, for reports that need more
+/// than the totals, such as [`format_score_matrix`](crate::format_score_matrix).
+pub fn tournament_results(entrants: &[Entrant], self_play: bool) -> Vec<GameResult> {
+    pairings(entrants.len(), self_play, false)
+        .into_iter()
+        .map(|(i, j)| {
+            let mut game = new_game(&entrants[i], &entrants[j]);
+            game.play();
+            game.result()
+        })
+        .collect()
+}
+
+/// The same as [`tournament_results`], but with the games played as
+/// [`run_tournament_with_config`] would play them.
+pub fn tournament_results_with_config(
+    entrants: &[Entrant],
+    config: &TournamentConfig,
+) -> Vec<GameResult> {
+    tournament_stream(entrants, config).collect()
+}
+
+/// The indices of the two entrants in each game of a round-robin, in the order they are played.
+/// In a double round-robin every game between two different entrants is followed by a rematch
+/// with their positions swapped.
+pub(crate) fn pairings(
+    entrants: usize,
+    self_play: bool,
+    double_round_robin: bool,
+) -> Vec<(usize, usize)> {
+    (0..entrants)
+        .flat_map(|i| (i..entrants).map(move |j| (i, j)))
+        .filter(|&(i, j)| i != j || self_play)
+        .flat_map(|(i, j)| {
+            let rematch = (double_round_robin && i != j).then_some((j, i));
+            [(i, j)].into_iter().chain(rematch)
+        })
+        .collect()
+}
+
+/// What ranking the entrants needs to know about one game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct Played {
+    pub(crate) scores: (isize, isize),
+    /// The number of times player 1 and player 2 cooperated
+    pub(crate) cooperations: (usize, usize),
+    pub(crate) rounds: usize,
+}
+
+impl Played {
+    pub(crate) fn of(game: &DynGame) -> Self {
+        let cooperations =
+            |moves: &[CooperateOrDefect]| moves.iter().filter(|&&m| m == Cooperate).count();
+        Self {
+            scores: game.calculate_score(),
+            cooperations: (
+                cooperations(game.player_1_moves()),
+                cooperations(game.player_2_moves()),
+            ),
+            rounds: game.player_1_moves().len(),
+        }
+    }
+}
+
+/// Total up each entrant's scores from the given games and rank the entrants, breaking ties
+/// with `tie_break`.
+pub(crate) fn tally(
+    entrants: &[Entrant],
+    pairings: &[(usize, usize)],
+    games: &[Played],
+    tie_break: TieBreak,
+) -> Vec<(&'static str, isize)> {
+    let scores = games.iter().map(|game| game.scores).collect::<Vec<_>>();
+    let totals = tally_unsorted(entrants, pairings, &scores);
+    let mut order = (0..entrants.len()).collect::<Vec<_>>();
+    let by_total = |&a: &usize, &b: &usize| totals[b].1.cmp(&totals[a].1);
+
+    match tie_break {
+        TieBreak::EntryOrder => order.sort_by(by_total),
+        TieBreak::Name => {
+            order.sort_by(|a, b| by_total(a, b).then_with(|| totals[*a].0.cmp(totals[*b].0)))
+        }
+        TieBreak::CooperationRate => {
+            // Games against itself only count once, as they do for the scores
+            let mut cooperations = vec![(0, 0); entrants.len()];
+            for (&(i, j), game) in pairings.iter().zip(games) {
+                cooperations[i].0 += game.cooperations.0;
+                cooperations[i].1 += game.rounds;
+                if i != j {
+                    cooperations[j].0 += game.cooperations.1;
+                    cooperations[j].1 += game.rounds;
+                }
+            }
+            let rates = cooperations
+                .iter()
+                .map(|&(cooperated, rounds)| cooperated as f64 / rounds.max(1) as f64)
+                .collect::<Vec<_>>();
+            order.sort_by(|a, b| by_total(a, b).then_with(|| rates[*b].total_cmp(&rates[*a])));
+        }
+        TieBreak::HeadToHead => {
+            // How far each entrant outscored the entrants it is tied with, in their games
+            let mut margins = vec![0isize; entrants.len()];
+            for (&(i, j), game) in pairings.iter().zip(games) {
+                if i != j && totals[i].1 == totals[j].1 {
+                    let (i_score, j_score) = game.scores;
+                    margins[i] = margins[i].saturating_add(i_score.saturating_sub(j_score));
+                    margins[j] = margins[j].saturating_add(j_score.saturating_sub(i_score));
+                }
+            }
+            order.sort_by(|a, b| by_total(a, b).then_with(|| margins[*b].cmp(&margins[*a])));
+        }
+    }
+
+    order.into_iter().map(|i| totals[i]).collect()
+}
+
+/// Total up each entrant's scores from the given games, in the order the entrants were entered.
+fn tally_unsorted(
+    entrants: &[Entrant],
+    pairings: &[(usize, usize)],
+    scores: &[(isize, isize)],
+) -> Vec<(&'static str, isize)> {
+    let mut totals: Vec<(&'static str, isize)> = entrants.iter().map(|e| (e.name, 0)).collect();
+
+    for (&(i, j), &(p1_score, p2_score)) in pairings.iter().zip(scores) {
+        totals[i].1 = totals[i].1.saturating_add(p1_score);
+        if i != j {
+            totals[j].1 = totals[j].1.saturating_add(p2_score);
+        }
+    }
+
+    totals
+}
+
+/// Which player won a game, see [`head_to_head`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Winner {
+    P1,
+    P2,
+    Draw,
+}
+
+impl Winner {
+    /// The winner of a game that ended with player 1 and player 2 on these scores.
+    pub fn from_scores((p1_score, p2_score): (isize, isize)) -> Self {
+        match p1_score.cmp(&p2_score) {
+            std::cmp::Ordering::Greater => Winner::P1,
+            std::cmp::Ordering::Less => Winner::P2,
+            std::cmp::Ordering::Equal => Winner::Draw,
+        }
+    }
+}
+
+/// Play one game between `P1` and `P2` with the rounds, noise, and seed in `config`, and say
+/// which of them finished with the higher score, along with the full result of the game. The
+/// game is the same one that the two would play in a tournament of just the pair of them.
+pub fn head_to_head<P1, P2>(config: &TournamentConfig) -> (Winner, GameResult)
+where
+    P1: Strategy + 'static,
+    P1::State: 'static,
+    P2: Strategy + 'static,
+    P2::State: 'static,
+{
+    let entrants = [Entrant::new::<P1>(), Entrant::new::<P2>()];
+    let result = play_seeded_game(&entrants, 0, 1, config).result();
+    (
+        Winner::from_scores((result.p1_score, result.p2_score)),
+        result,
+    )
+}
+
+/// Play a single game of `NUM_TURNS` rounds between two entrants and return the final score.
+pub(crate) fn play_game(entrant_1: &Entrant, entrant_2: &Entrant) -> (isize, isize) {
+    let mut game = new_game(entrant_1, entrant_2);
+    game.play();
+    game.calculate_score()
+}
+
+fn new_game(entrant_1: &Entrant, entrant_2: &Entrant) -> DynGame {
+    DynGame::new(entrant_1.new_player(0), entrant_2.new_player(0))
+}
+
+/// Play the game between the entrants at positions `i` and `j` with the settings and seeds that
+/// `config` gives it.
+pub(crate) fn play_seeded_game(
+    entrants: &[Entrant],
+    i: usize,
+    j: usize,
+    config: &TournamentConfig,
+) -> DynGame {
+    let mut game = seeded_game(entrants, i, j, config);
+    game.play_n_rounds(config.rounds);
+    game
+}
+
+/// The game between entrants `i` and `j` that [`play_seeded_game`] plays, before any rounds.
+fn seeded_game(entrants: &[Entrant], i: usize, j: usize, config: &TournamentConfig) -> DynGame {
+    // Mix the positions into the seed so that every game gets unrelated seeds
+    let mut rng = Rng::new(Rng::new(config.seed).next_u64() ^ ((i as u64) << 32 | j as u64));
+    let (p1_seed, p2_seed, noise_seed) = (rng.next_u64(), rng.next_u64(), rng.next_u64());
+
+    let mut game = DynGame::new(
+        entrants[i].new_player(p1_seed),
+        entrants[j].new_player(p2_seed),
+    );
+    game.set_noise(config.noise, noise_seed);
+    game
+}
@@ -0,0 +1,135 @@
+//! Strategies compiled to WebAssembly, so that they can be written in any language. Enabled by
+//! the `wasm` feature.
+//!
+//! A module takes part by exporting `next_move(my_ptr, my_len, their_ptr, their_len) -> i32`.
+//! Before each call both histories are written into the module's memory one byte per move, see
+//! [`encode_moves`], and the result uses the same encoding, see [`decode_move`]. This crate does
+//! not include a WebAssembly runtime. One is plugged in by implementing [`WasmModule`] for its
+//! module instances, which [`WasmStrategy`] then turns into a player.
+
+use crate::{CooperateOrDefect, CooperateOrDefect::*, DynStrategy, HistoryView, StrategyError};
+
+/// How a cooperation is passed to and from a module.
+pub const WASM_COOPERATE: u8 = 0;
+
+/// How a defection is passed to and from a module.
+pub const WASM_DEFECT: u8 = 1;
+
+/// A history of moves as it is written into a module's memory, one byte per move.
+pub fn encode_moves(moves: &[CooperateOrDefect]) -> Vec<u8> {
+    moves
+        .iter()
+        .map(|next_move| match next_move {
+            Cooperate => WASM_COOPERATE,
+            Defect => WASM_DEFECT,
+        })
+        .collect()
+}
+
+/// The move a module chose, from the value its `next_move` returned.
+pub fn decode_move(value: i32) -> Result<CooperateOrDefect, StrategyError> {
+    match value {
+        v if v == WASM_COOPERATE as i32 => Ok(Cooperate),
+        v if v == WASM_DEFECT as i32 => Ok(Defect),
+        other => Err(StrategyError(format!(
+            "the module returned {other}, which is not a move"
+        ))),
+    }
+}
+
+/// An instance of a module loaded by some WebAssembly runtime.
+pub trait WasmModule {
+    /// Copy the encoded histories into the module's memory, call its exported `next_move` with
+    /// their addresses and lengths, and return the result, or a description of what went wrong,
+    /// such as a trap or a missing export.
+    fn call_next_move(&mut self, my_moves: &[u8], their_moves: &[u8]) -> Result<i32, String>;
+}
+
+/// A player whose moves are chosen by a WebAssembly module.
+///
+/// A module that traps or returns something other than a move forfeits the round, see
+/// [`DynStrategy::try_next_move`]. Here a stand-in for a module implementing Always Defect, as
+/// a runtime would provide it, is played against Always Cooperate:
+///
+/// ```
+/// # use prisoners_dilemma_competition::*;
+/// struct AlwaysDefectModule;
+///
+/// impl WasmModule for AlwaysDefectModule {
+///     fn call_next_move(&mut self, _mine: &[u8], _theirs: &[u8]) -> Result<i32, String> {
+///         Ok(WASM_DEFECT as i32)
+///     }
+/// }
+///
+/// let mut game = DynGame::new(
+///     Box::new(WasmStrategy::new("Wasm Always Defect", AlwaysDefectModule)),
+///     StrategyInstance::<AlwaysCooperate>::boxed(),
+/// );
+/// game.play_n_rounds(10);
+/// assert_eq!(game.cooperation_rates(), (0.0, 1.0));
+/// assert!(game.failures().is_empty());
+///
+/// struct Broken;
+///
+/// impl WasmModule for Broken {
+///     fn call_next_move(&mut self, _mine: &[u8], _theirs: &[u8]) -> Result<i32, String> {
+///         Ok(7)
+///     }
+/// }
+///
+/// let mut game = DynGame::new(
+///     Box::new(WasmStrategy::new("Broken", Broken)),
+///     StrategyInstance::<AlwaysCooperate>::boxed(),
+/// );
+/// game.play_n_rounds(3);
+/// assert_eq!(game.failures().len(), 3);
+/// ```
+pub struct WasmStrategy<M> {
+    name: String,
+    module: M,
+}
+
+impl<M: WasmModule> WasmStrategy<M> {
+    pub fn new(name: impl Into<String>, module: M) -> Self {
+        Self {
+            name: name.into(),
+            module,
+        }
+    }
+}
+
+impl<M: WasmModule> DynStrategy for WasmStrategy<M> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Nothing is known about how the module chooses its moves, so its games are never reused.
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+
+    /// Games call [`try_next_move`](DynStrategy::try_next_move) instead. Called directly, this
+    /// defects whenever the module fails to choose a move.
+    fn next_move(
+        &mut self,
+        my_moves: &[CooperateOrDefect],
+        their_moves: &[CooperateOrDefect],
+    ) -> CooperateOrDefect {
+        self.try_next_move(&HistoryView::new(my_moves, their_moves))
+            .unwrap_or(Defect)
+    }
+
+    fn try_next_move(
+        &mut self,
+        history: &HistoryView<'_>,
+    ) -> Result<CooperateOrDefect, StrategyError> {
+        let value = self
+            .module
+            .call_next_move(
+                &encode_moves(history.my_moves),
+                &encode_moves(history.their_moves),
+            )
+            .map_err(StrategyError)?;
+        decode_move(value)
+    }
+}